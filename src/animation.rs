@@ -0,0 +1,84 @@
+use bevy::prelude::*;
+use std::ops::RangeInclusive;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub(crate) enum AnimationState {
+    #[default]
+    Idle,
+    Walk,
+    IdleUp,
+    IdleDown,
+    IdleLeft,
+    IdleRight,
+    WalkUp,
+    WalkDown,
+    WalkLeft,
+    WalkRight,
+}
+
+impl AnimationState {
+    pub(crate) fn idle(self) -> Self {
+        match self {
+            AnimationState::Walk => AnimationState::Idle,
+            AnimationState::WalkUp => AnimationState::IdleUp,
+            AnimationState::WalkDown => AnimationState::IdleDown,
+            AnimationState::WalkLeft => AnimationState::IdleLeft,
+            AnimationState::WalkRight => AnimationState::IdleRight,
+            idle => idle,
+        }
+    }
+}
+
+#[derive(Component)]
+pub(crate) struct SpriteAnimation {
+    clips: Vec<(AnimationState, RangeInclusive<usize>)>,
+    pub(crate) state: AnimationState,
+    timer: Timer,
+}
+
+impl SpriteAnimation {
+    pub(crate) fn new(fps: f32, clips: Vec<(AnimationState, RangeInclusive<usize>)>) -> Self {
+        SpriteAnimation {
+            clips,
+            state: AnimationState::default(),
+            timer: Timer::from_seconds(1.0 / fps, TimerMode::Repeating),
+        }
+    }
+
+    fn frames(&self) -> Option<&RangeInclusive<usize>> {
+        self.clips
+            .iter()
+            .find(|(state, _)| *state == self.state)
+            .map(|(_, frames)| frames)
+    }
+}
+
+impl Default for SpriteAnimation {
+    fn default() -> Self {
+        SpriteAnimation::new(1.0, Vec::new())
+    }
+}
+
+pub(crate) fn animate_sprites(
+    mut query: Query<(&mut SpriteAnimation, &mut TextureAtlas)>,
+    time: Res<Time>,
+) {
+    for (mut animation, mut atlas) in &mut query {
+        animation.timer.tick(time.delta());
+
+        let Some(frames) = animation.frames().cloned() else {
+            continue;
+        };
+
+        if !frames.contains(&atlas.index) {
+            atlas.index = *frames.start();
+            animation.timer.reset();
+        } else if animation.timer.just_finished() {
+            atlas.index = if atlas.index >= *frames.end() {
+                *frames.start()
+            } else {
+                atlas.index + 1
+            };
+        }
+    }
+}
@@ -3,6 +3,7 @@ use bevy_ecs_ldtk::prelude::*;
 use std::collections::HashSet;
 
 use crate::consts::*;
+use crate::pathfinding::Pathfinder;
 
 #[derive(Default, Component)]
 pub(crate) struct Collision;
@@ -12,6 +13,24 @@ pub(crate) struct CollisionBundle {
     collision: Collision,
 }
 
+#[derive(Default, Component)]
+pub(crate) struct Hitbox {
+    pub(crate) half_size: Vec2,
+}
+
+impl Hitbox {
+    pub(crate) fn new(half_size: Vec2) -> Self {
+        Hitbox { half_size }
+    }
+
+    pub(crate) fn overlaps(&self, position: Vec2, other: &Hitbox, other_position: Vec2) -> bool {
+        let distance = (position - other_position).abs();
+        let reach = self.half_size + other.half_size;
+
+        distance.x < reach.x && distance.y < reach.y
+    }
+}
+
 #[derive(Default, Resource)]
 pub(crate) struct LevelCollisions {
     collision_locations: HashSet<GridCoords>,
@@ -27,10 +46,126 @@ impl LevelCollisions {
             || grid_coords.y >= self.level_height
             || self.collision_locations.contains(grid_coords)
     }
+
+    #[cfg(test)]
+    pub(crate) fn from_rows(rows: &[&str]) -> Self {
+        let level_height = rows.len() as i32;
+        let level_width = rows[0].len() as i32;
+        let collision_locations = rows
+            .iter()
+            .enumerate()
+            .flat_map(|(row, line)| {
+                line.chars()
+                    .enumerate()
+                    .filter(|(_, tile)| *tile == '#')
+                    .map(move |(x, _)| GridCoords::new(x as i32, level_height - 1 - row as i32))
+            })
+            .collect();
+
+        LevelCollisions {
+            collision_locations,
+            level_width,
+            level_height,
+        }
+    }
+
+    fn overlaps(&self, center: Vec2, half_size: Vec2) -> bool {
+        let grid = GRID_SIZE as f32;
+        let min = ((center - half_size) / grid).floor().as_ivec2();
+        let max = ((center + half_size) / grid).ceil().as_ivec2() - IVec2::ONE;
+
+        (min.x..=max.x).any(|x| (min.y..=max.y).any(|y| self.collision(&GridCoords::new(x, y))))
+    }
+
+    fn sweep_axis(&self, position: Vec2, half_size: Vec2, delta: f32, axis: usize) -> Vec2 {
+        let mut moved = position;
+        moved[axis] += delta;
+
+        if !self.overlaps(moved, half_size) {
+            return moved;
+        }
+
+        let grid = GRID_SIZE as f32;
+        moved[axis] = if delta > 0.0 {
+            let flush = ((moved[axis] + half_size[axis]) / grid).floor() * grid - half_size[axis];
+            flush.max(position[axis])
+        } else {
+            let flush = ((moved[axis] - half_size[axis]) / grid).ceil() * grid + half_size[axis];
+            flush.min(position[axis])
+        };
+
+        if self.overlaps(moved, half_size) {
+            position
+        } else {
+            moved
+        }
+    }
+
+    fn corner_nudge(
+        &self,
+        position: Vec2,
+        half_size: Vec2,
+        delta: f32,
+        axis: usize,
+    ) -> Option<Vec2> {
+        let other = 1 - axis;
+
+        for offset in 1..=CORNER_FORGIVENESS as i32 {
+            for sign in [-1.0, 1.0] {
+                let mut shifted = position;
+                shifted[other] += sign * offset as f32;
+                let mut moved = shifted;
+                moved[axis] += delta;
+
+                if !self.overlaps(shifted, half_size) && !self.overlaps(moved, half_size) {
+                    let mut nudged = position;
+                    nudged[other] += sign * (offset as f32).min(delta.abs());
+                    return Some(nudged);
+                }
+            }
+        }
+
+        None
+    }
+
+    pub(crate) fn line_of_sight(&self, from: Vec2, to: Vec2) -> bool {
+        let steps = (from.distance(to) / (GRID_SIZE as f32 / 4.0))
+            .ceil()
+            .max(1.0) as usize;
+
+        (0..=steps).all(|step| {
+            let point = from.lerp(to, step as f32 / steps as f32);
+            !self.collision(&bevy_ecs_ldtk::utils::translation_to_grid_coords(
+                point,
+                IVec2::splat(GRID_SIZE),
+            ))
+        })
+    }
+
+    pub(crate) fn move_and_slide(&self, position: Vec2, half_size: Vec2, delta: Vec2) -> Vec2 {
+        let mut position = position;
+
+        for axis in 0..2 {
+            if delta[axis] == 0.0 {
+                continue;
+            }
+
+            let moved = self.sweep_axis(position, half_size, delta[axis], axis);
+            position = if moved == position && delta[1 - axis] == 0.0 {
+                self.corner_nudge(position, half_size, delta[axis], axis)
+                    .unwrap_or(moved)
+            } else {
+                moved
+            };
+        }
+
+        position
+    }
 }
 
 pub(crate) fn cache_collision_locations(
     mut level_collisions: ResMut<LevelCollisions>,
+    mut pathfinder: ResMut<Pathfinder>,
     mut level_events: EventReader<LevelEvent>,
     collision_grid_pos: Query<&GridCoords, With<Collision>>,
     ldtk_project_entities: Query<&Handle<LdtkProject>>,
@@ -53,6 +188,105 @@ pub(crate) fn cache_collision_locations(
                 level_width: level.px_wid / GRID_SIZE,
                 level_height: level.px_hei / GRID_SIZE,
             };
+            pathfinder.invalidate();
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HALF_SIZE: Vec2 = Vec2::new(6.0, 6.0);
+
+    fn cell_center(x: i32, y: i32) -> Vec2 {
+        (Vec2::new(x as f32, y as f32) + 0.5) * GRID_SIZE as f32
+    }
+
+    #[test]
+    fn open_space_moves_freely() {
+        let level = LevelCollisions::from_rows(&["...", "...", "..."]);
+        let start = cell_center(1, 1);
+
+        assert_eq!(
+            level.move_and_slide(start, HALF_SIZE, Vec2::new(3.0, -2.0)),
+            start + Vec2::new(3.0, -2.0)
+        );
+    }
+
+    #[test]
+    fn walls_stop_the_box_flush() {
+        let level = LevelCollisions::from_rows(&["...", ".#.", "..."]);
+        let start = cell_center(0, 1);
+
+        let moved = level.move_and_slide(start, HALF_SIZE, Vec2::new(4.0, 0.0));
+
+        assert_eq!(moved, Vec2::new(GRID_SIZE as f32 - HALF_SIZE.x, start.y));
+        assert_eq!(
+            level.move_and_slide(moved, HALF_SIZE, Vec2::new(4.0, 0.0)),
+            moved
+        );
+    }
+
+    #[test]
+    fn level_bounds_block_movement() {
+        let level = LevelCollisions::from_rows(&["..", ".."]);
+        let start = Vec2::new(HALF_SIZE.x + 1.0, cell_center(0, 0).y);
+
+        assert_eq!(
+            level.move_and_slide(start, HALF_SIZE, Vec2::new(-4.0, 0.0)),
+            Vec2::new(HALF_SIZE.x, start.y)
+        );
+    }
+
+    #[test]
+    fn diagonal_movement_slides_along_walls() {
+        let level = LevelCollisions::from_rows(&["...", "...", "###"]);
+        let start = Vec2::new(cell_center(1, 1).x, GRID_SIZE as f32 + HALF_SIZE.y);
+
+        assert_eq!(
+            level.move_and_slide(start, HALF_SIZE, Vec2::new(3.0, -3.0)),
+            start + Vec2::new(3.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn diagonal_movement_does_not_clip_corners() {
+        let level = LevelCollisions::from_rows(&["...", ".#.", "..."]);
+        let start = Vec2::new(
+            GRID_SIZE as f32 - HALF_SIZE.x,
+            2.0 * GRID_SIZE as f32 + HALF_SIZE.y,
+        );
+
+        let moved = level.move_and_slide(start, HALF_SIZE, Vec2::new(4.0, -4.0));
+
+        assert!(!level.overlaps(moved, HALF_SIZE));
+    }
+
+    #[test]
+    fn corners_are_forgiven_by_nudging_around_them() {
+        let level = LevelCollisions::from_rows(&["#.#", "#.#", "..."]);
+        let start = Vec2::new(
+            GRID_SIZE as f32 + HALF_SIZE.x - 2.0,
+            GRID_SIZE as f32 - HALF_SIZE.y,
+        );
+
+        let moved = level.move_and_slide(start, HALF_SIZE, Vec2::new(0.0, 4.0));
+
+        assert_eq!(moved, start + Vec2::new(2.0, 0.0));
+    }
+
+    #[test]
+    fn corners_beyond_the_forgiveness_stay_blocked() {
+        let level = LevelCollisions::from_rows(&["#.#", "#.#", "..."]);
+        let start = Vec2::new(
+            GRID_SIZE as f32 + HALF_SIZE.x - CORNER_FORGIVENESS - 1.0,
+            GRID_SIZE as f32 - HALF_SIZE.y,
+        );
+
+        assert_eq!(
+            level.move_and_slide(start, HALF_SIZE, Vec2::new(0.0, 4.0)),
+            start
+        );
+    }
+}
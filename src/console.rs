@@ -0,0 +1,409 @@
+use bevy::{
+    input::{
+        keyboard::{Key, KeyboardInput},
+        ButtonState, InputSystem,
+    },
+    prelude::*,
+};
+use bevy_ecs_ldtk::prelude::*;
+
+use crate::consts::*;
+use crate::enemies::{Cow, CowBundle};
+use crate::input::{self, ActionState};
+use crate::levels::LdtkProjectHandle;
+use crate::player::{Player, PlayerStats};
+use crate::simulation::SimulatedPosition;
+
+const CONSOLE_COLOR: Color = Color::srgba(0.1, 0.1, 0.1, 0.85);
+const CONSOLE_LOG_LINES: usize = 12;
+const SPAWNED_COW_PATROL_CELLS: i32 = 3;
+
+#[derive(Debug, PartialEq, Event)]
+pub(crate) enum ConsoleCommand {
+    Help,
+    Level(usize),
+    God,
+    NoClip,
+    GiveKeys(usize),
+    Score(f32),
+    Heal,
+    SpawnCow,
+    Teleport(i32, i32),
+}
+
+impl ConsoleCommand {
+    fn parse(line: &str) -> Result<Self, String> {
+        let words = line.split_whitespace().collect::<Vec<_>>();
+
+        fn number<T: std::str::FromStr>(word: Option<&&str>) -> Result<T, String> {
+            word.ok_or_else(|| "missing number".to_string())?
+                .parse()
+                .map_err(|_| "invalid number".to_string())
+        }
+
+        match words.as_slice() {
+            ["help"] => Ok(ConsoleCommand::Help),
+            ["level", rest @ ..] => Ok(ConsoleCommand::Level(number(rest.first())?)),
+            ["god"] => Ok(ConsoleCommand::God),
+            ["noclip"] => Ok(ConsoleCommand::NoClip),
+            ["give", "key", rest @ ..] => Ok(ConsoleCommand::GiveKeys(
+                rest.first().map_or(Ok(1), |_| number(rest.first()))?,
+            )),
+            ["score", rest @ ..] => Ok(ConsoleCommand::Score(number(rest.first())?)),
+            ["heal"] => Ok(ConsoleCommand::Heal),
+            ["spawn", "cow"] => Ok(ConsoleCommand::SpawnCow),
+            ["tp", rest @ ..] => Ok(ConsoleCommand::Teleport(
+                number(rest.first())?,
+                number(rest.get(1))?,
+            )),
+            [] => Err("empty command".to_string()),
+            _ => Err(format!("unknown command: {}", line.trim())),
+        }
+    }
+}
+
+#[derive(Default, Resource)]
+pub(crate) struct DevConsole {
+    open: bool,
+    input: String,
+    history: Vec<String>,
+    history_index: Option<usize>,
+    log: Vec<String>,
+}
+
+impl DevConsole {
+    fn log(&mut self, line: impl Into<String>) {
+        let line = line.into();
+        info!("{}", line);
+        self.log.push(line);
+
+        if self.log.len() > CONSOLE_LOG_LINES {
+            self.log.remove(0);
+        }
+    }
+}
+
+#[derive(Default, Resource)]
+pub(crate) struct DevCheats {
+    pub(crate) god: bool,
+    pub(crate) noclip: bool,
+}
+
+#[derive(Component)]
+pub(crate) struct ConsoleUi;
+
+#[derive(Component)]
+pub(crate) struct ConsoleText;
+
+pub(crate) struct DevConsolePlugin;
+
+impl Plugin for DevConsolePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DevConsole>()
+            .init_resource::<DevCheats>()
+            .add_event::<ConsoleCommand>()
+            .add_systems(Startup, setup_console)
+            .add_systems(
+                PreUpdate,
+                (toggle_console, read_console_input, suppress_actions)
+                    .chain()
+                    .after(InputSystem)
+                    .after(input::update_action_state),
+            )
+            .add_systems(Update, (run_console_commands, update_console).chain());
+    }
+}
+
+fn setup_console(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                padding: UiRect::all(Val::Percent(1.0)),
+                ..default()
+            },
+            background_color: CONSOLE_COLOR.into(),
+            visibility: Visibility::Hidden,
+            z_index: ZIndex::Global(20),
+            ..default()
+        })
+        .insert(ConsoleUi)
+        .with_children(|parent| {
+            parent
+                .spawn(TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font: asset_server.load("fonts/FiraCodeNerdFont-Regular.ttf"),
+                        font_size: 30.0,
+                        color: TEXT_COLOR,
+                    },
+                ))
+                .insert(ConsoleText);
+        });
+}
+
+fn toggle_console(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut dev_console: ResMut<DevConsole>,
+    mut console_ui: Query<&mut Visibility, With<ConsoleUi>>,
+) {
+    if keyboard.just_pressed(KeyCode::Backquote) {
+        dev_console.open = !dev_console.open;
+
+        if let Ok(mut visibility) = console_ui.get_single_mut() {
+            *visibility = if dev_console.open {
+                Visibility::Visible
+            } else {
+                Visibility::Hidden
+            };
+        }
+    }
+}
+
+fn read_console_input(
+    mut keyboard_events: EventReader<KeyboardInput>,
+    mut dev_console: ResMut<DevConsole>,
+    mut console_commands: EventWriter<ConsoleCommand>,
+) {
+    if !dev_console.open {
+        keyboard_events.clear();
+        return;
+    }
+
+    for keyboard_event in keyboard_events.read() {
+        if keyboard_event.state != ButtonState::Pressed {
+            continue;
+        }
+
+        match &keyboard_event.logical_key {
+            Key::Character(characters) => {
+                for character in characters.chars() {
+                    if !matches!(character, '`' | '~') {
+                        dev_console.input.push(character);
+                    }
+                }
+            }
+            Key::Space => dev_console.input.push(' '),
+            Key::Backspace => {
+                dev_console.input.pop();
+            }
+            Key::ArrowUp if !dev_console.history.is_empty() => {
+                let index = dev_console
+                    .history_index
+                    .map_or(dev_console.history.len() - 1, |index| {
+                        index.saturating_sub(1)
+                    });
+                dev_console.history_index = Some(index);
+                dev_console.input = dev_console.history[index].clone();
+            }
+            Key::ArrowDown => {
+                if let Some(index) = dev_console.history_index {
+                    if index + 1 < dev_console.history.len() {
+                        dev_console.history_index = Some(index + 1);
+                        dev_console.input = dev_console.history[index + 1].clone();
+                    } else {
+                        dev_console.history_index = None;
+                        dev_console.input.clear();
+                    }
+                }
+            }
+            Key::Enter => {
+                let line = std::mem::take(&mut dev_console.input);
+                dev_console.history_index = None;
+                dev_console.log(format!("> {}", line));
+
+                if !line.trim().is_empty() {
+                    dev_console.history.push(line.clone());
+                }
+
+                match ConsoleCommand::parse(&line) {
+                    Ok(console_command) => {
+                        console_commands.send(console_command);
+                    }
+                    Err(err) => dev_console.log(err),
+                }
+            }
+            _ => (),
+        }
+    }
+}
+
+fn suppress_actions(dev_console: Res<DevConsole>, mut action_state: ResMut<ActionState>) {
+    if dev_console.open {
+        *action_state = ActionState::default();
+    }
+}
+
+fn run_console_commands(
+    mut console_commands: EventReader<ConsoleCommand>,
+    mut dev_console: ResMut<DevConsole>,
+    mut dev_cheats: ResMut<DevCheats>,
+    mut player_stats: ResMut<PlayerStats>,
+    mut level_selection: ResMut<LevelSelection>,
+    ldtk_project_handle: Res<LdtkProjectHandle>,
+    ldtk_project_assets: Res<Assets<LdtkProject>>,
+    mut player: Query<(&mut SimulatedPosition, &mut GridCoords), With<Player>>,
+    cows: Query<(&Transform, &Handle<Image>, &TextureAtlas, &Sprite, &Parent), With<Cow>>,
+    mut commands: Commands,
+) {
+    for console_command in console_commands.read() {
+        match *console_command {
+            ConsoleCommand::Help => dev_console.log(
+                "level <n>, god, noclip, give key <n>, score <n>, heal, spawn cow, tp <x> <y>",
+            ),
+            ConsoleCommand::Level(level) => {
+                let levels = ldtk_project_handle
+                    .levels(&ldtk_project_assets)
+                    .unwrap_or_default();
+
+                match levels.get(level) {
+                    Some(ldtk_level) => {
+                        player_stats.start_next_level();
+                        *level_selection = LevelSelection::index(level);
+                        dev_console.log(format!("loading {}", ldtk_level.identifier));
+                    }
+                    None => dev_console.log(format!("no level {}", level)),
+                }
+            }
+            ConsoleCommand::God => {
+                dev_cheats.god = !dev_cheats.god;
+                dev_console.log(format!("god mode {}", on_off(dev_cheats.god)));
+            }
+            ConsoleCommand::NoClip => {
+                dev_cheats.noclip = !dev_cheats.noclip;
+                dev_console.log(format!("noclip {}", on_off(dev_cheats.noclip)));
+            }
+            ConsoleCommand::GiveKeys(keys) => {
+                player_stats.keys += keys;
+                player_stats.keys_collected += keys;
+                dev_console.log(format!("gave {} key(s)", keys));
+            }
+            ConsoleCommand::Score(score) => {
+                player_stats.score = score;
+                dev_console.log(format!("score set to {}", score));
+            }
+            ConsoleCommand::Heal => {
+                player_stats.health = PLAYER_MAX_HEALTH;
+                dev_console.log("healed");
+            }
+            ConsoleCommand::SpawnCow => {
+                let Ok((player_position, _)) = player.get_single() else {
+                    dev_console.log("no player to spawn next to");
+                    continue;
+                };
+                let Some((transform, texture, atlas, sprite, parent)) = cows.iter().next() else {
+                    dev_console.log("no cow in this level to copy");
+                    continue;
+                };
+
+                let translation = (player_position.current.truncate() + Vec2::X * GRID_SIZE as f32)
+                    .extend(transform.translation.z);
+                let sprite_sheet_bundle = LdtkSpriteSheetBundle {
+                    sprite_bundle: SpriteBundle {
+                        texture: texture.clone(),
+                        sprite: sprite.clone(),
+                        transform: Transform::from_translation(translation),
+                        ..default()
+                    },
+                    texture_atlas: atlas.clone(),
+                };
+                let patrol_end = translation.truncate()
+                    + Vec2::X * (GRID_SIZE * SPAWNED_COW_PATROL_CELLS) as f32;
+                commands
+                    .spawn(CowBundle::new(
+                        sprite_sheet_bundle,
+                        translation,
+                        vec![patrol_end],
+                    ))
+                    .set_parent(parent.get());
+                dev_console.log("spawned a cow");
+            }
+            ConsoleCommand::Teleport(x, y) => {
+                let Ok((mut player_position, mut player_grid_pos)) = player.get_single_mut() else {
+                    dev_console.log("no player to teleport");
+                    continue;
+                };
+
+                let grid_coords = GridCoords::new(x, y);
+                let translation = bevy_ecs_ldtk::utils::grid_coords_to_translation(
+                    grid_coords,
+                    IVec2::new(GRID_SIZE, GRID_SIZE),
+                )
+                .extend(player_position.current.z);
+                player_position.teleport(translation);
+                *player_grid_pos = grid_coords;
+                dev_console.log(format!("teleported to {} {}", x, y));
+            }
+        }
+    }
+}
+
+fn on_off(enabled: bool) -> &'static str {
+    if enabled {
+        "on"
+    } else {
+        "off"
+    }
+}
+
+fn update_console(
+    dev_console: Res<DevConsole>,
+    mut console_text: Query<&mut Text, With<ConsoleText>>,
+) {
+    if !dev_console.is_changed() {
+        return;
+    }
+
+    if let Ok(mut text) = console_text.get_single_mut() {
+        text.sections[0].value = dev_console
+            .log
+            .iter()
+            .cloned()
+            .chain([format!("> {}_", dev_console.input)])
+            .collect::<Vec<_>>()
+            .join("\n");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn commands_are_parsed_with_arguments() {
+        assert_eq!(
+            ConsoleCommand::parse("level 3"),
+            Ok(ConsoleCommand::Level(3))
+        );
+        assert_eq!(
+            ConsoleCommand::parse("give key 2"),
+            Ok(ConsoleCommand::GiveKeys(2))
+        );
+        assert_eq!(
+            ConsoleCommand::parse("give key"),
+            Ok(ConsoleCommand::GiveKeys(1))
+        );
+        assert_eq!(
+            ConsoleCommand::parse("score 1500"),
+            Ok(ConsoleCommand::Score(1500.0))
+        );
+        assert_eq!(
+            ConsoleCommand::parse(" tp 4  -2 "),
+            Ok(ConsoleCommand::Teleport(4, -2))
+        );
+        assert_eq!(
+            ConsoleCommand::parse("spawn cow"),
+            Ok(ConsoleCommand::SpawnCow)
+        );
+    }
+
+    #[test]
+    fn invalid_commands_are_rejected() {
+        assert!(ConsoleCommand::parse("").is_err());
+        assert!(ConsoleCommand::parse("level").is_err());
+        assert!(ConsoleCommand::parse("level x").is_err());
+        assert!(ConsoleCommand::parse("tp 1").is_err());
+        assert!(ConsoleCommand::parse("fly").is_err());
+    }
+}
@@ -1,4 +1,5 @@
-use bevy::color::Color;
+use bevy::{color::Color, math::Vec2};
+use std::ops::RangeInclusive;
 
 pub(crate) const APP_NAME: &str = "GLULANDS";
 pub(crate) const VERSION: &str = "v0.4.2";
@@ -9,6 +10,16 @@ pub(crate) const PHYSICAL_HEIGHT: f32 = 900.0;
 pub(crate) const SCALE: f32 = 0.2;
 pub(crate) const GRID_SIZE: i32 = 16;
 pub(crate) const LDTK_PROJECT_PATH: &str = "Glulands.ldtk";
+pub(crate) const DATA_DIR_NAME: &str = "glulands";
+pub(crate) const SAVE_FILE_NAME: &str = "save.json";
+pub(crate) const HIGH_SCORES_FILE_NAME: &str = "highscores.json";
+pub(crate) const MAX_HIGH_SCORES: usize = 10;
+pub(crate) const MAX_NAME_LENGTH: usize = 12;
+pub(crate) const NAME_CHARACTERS: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789 ";
+pub(crate) const PROGRESS_FILE_NAME: &str = "progress.json";
+pub(crate) const SETTINGS_FILE_NAME: &str = "settings.json";
+pub(crate) const GAMEPAD_AXIS_THRESHOLD: f32 = 0.5;
+pub(crate) const TOUCH_BUTTON_SIZE: f32 = 12.0;
 
 pub(crate) const BACKGROUND_MUSIC_PATH: &str = "sounds/Intergalactic Odyssey.ogg";
 pub(crate) const BACKGROUND_MUSIC_VOLUME: f32 = 0.3;
@@ -22,17 +33,78 @@ pub(crate) const BACKGROUND_COLOR: Color = Color::srgb(0.15, 0.15, 0.15);
 pub(crate) const BAR_COLOR: Color = Color::srgb(0.25, 0.25, 0.25);
 pub(crate) const TEXT_COLOR: Color = Color::srgb(0.1, 1.0, 0.7);
 pub(crate) const GAME_OVER_COLOR: Color = Color::srgb(0.7, 0.2, 0.3);
+pub(crate) const HURT_COLOR: Color = Color::srgb(1.0, 0.3, 0.3);
+pub(crate) const STUNNED_COLOR: Color = Color::srgb(0.6, 0.6, 1.0);
+pub(crate) const TOUCH_BUTTON_COLOR: Color = Color::srgba(0.25, 0.25, 0.25, 0.6);
 
-pub(crate) const PLAYER_ATLAS_INDEX_LEFT: usize = 452;
-pub(crate) const PLAYER_ATLAS_INDEX_RIGHT: usize = 644;
-pub(crate) const PLAYER_ATLAS_INDEX_UP: usize = 257;
-pub(crate) const PLAYER_ATLAS_INDEX_DOWN: usize = 68;
+pub(crate) const PLAYER_ANIMATION_FPS: f32 = 8.0;
+pub(crate) const PLAYER_IDLE_DOWN_FRAMES: RangeInclusive<usize> = 0..=1;
+pub(crate) const PLAYER_IDLE_UP_FRAMES: RangeInclusive<usize> = 4..=5;
+pub(crate) const PLAYER_IDLE_LEFT_FRAMES: RangeInclusive<usize> = 8..=9;
+pub(crate) const PLAYER_IDLE_RIGHT_FRAMES: RangeInclusive<usize> = 12..=13;
+pub(crate) const PLAYER_WALK_DOWN_FRAMES: RangeInclusive<usize> = 0..=3;
+pub(crate) const PLAYER_WALK_UP_FRAMES: RangeInclusive<usize> = 4..=7;
+pub(crate) const PLAYER_WALK_LEFT_FRAMES: RangeInclusive<usize> = 8..=11;
+pub(crate) const PLAYER_WALK_RIGHT_FRAMES: RangeInclusive<usize> = 12..=15;
+pub(crate) const PLAYER_HURT_FLASH_RATE: f32 = 20.0;
 pub(crate) const PLAYER_SPEED: f32 = 80.0;
 pub(crate) const PLAYER_MAX_HEALTH: f32 = 100.0;
 pub(crate) const COW_SPEED: f32 = 70.0;
+pub(crate) const COW_ANIMATION_FPS: f32 = 6.0;
+pub(crate) const COW_IDLE_FRAMES: RangeInclusive<usize> = 0..=2;
+pub(crate) const COW_WALK_FRAMES: RangeInclusive<usize> = 3..=5;
 pub(crate) const COW_HEALTH_HIT: f32 = 20.0;
 pub(crate) const COW_SCORE_HIT: f32 = 100.0;
+pub(crate) const COW_SIGHT_RADIUS: f32 = 5.0;
+pub(crate) const COW_CHASE_SPEED: f32 = 60.0;
+pub(crate) const COW_ALERT_TIME: f32 = 0.5;
+pub(crate) const COW_CHASE_TIMEOUT: f32 = 3.0;
+pub(crate) const COW_REPATH_INTERVAL: f32 = 0.5;
+pub(crate) const COW_BLOCKED_PROGRESS: f32 = 0.1;
+pub(crate) const COW_HITBOX_HALF_SIZE: Vec2 = Vec2::new(8.0, 6.0);
+pub(crate) const CHICKEN_SPRITE_PATH: &str = "Characters/Free Chicken Sprites.png";
+pub(crate) const CHICKEN_SPRITE_SIZE: u32 = 16;
+pub(crate) const CHICKEN_SPRITE_COLUMNS: u32 = 4;
+pub(crate) const CHICKEN_SPRITE_ROWS: u32 = 2;
+pub(crate) const CHICKEN_ANIMATION_FPS: f32 = 10.0;
+pub(crate) const CHICKEN_IDLE_FRAMES: RangeInclusive<usize> = 0..=1;
+pub(crate) const CHICKEN_WALK_FRAMES: RangeInclusive<usize> = 4..=7;
+pub(crate) const CHICKEN_SPEED: f32 = 110.0;
+pub(crate) const CHICKEN_WANDER_MIN: f32 = 0.2;
+pub(crate) const CHICKEN_WANDER_MAX: f32 = 0.7;
+pub(crate) const CHICKEN_HEALTH_HIT: f32 = 5.0;
+pub(crate) const CHICKEN_SCORE_HIT: f32 = 25.0;
+pub(crate) const CHICKEN_HITBOX_HALF_SIZE: Vec2 = Vec2::new(5.0, 4.0);
+pub(crate) const CHICKEN_SPAWN_INTERVAL: f32 = 3.0;
+pub(crate) const MAX_CHICKENS_PER_HOUSE: usize = 3;
+pub(crate) const THORNS_HEALTH_HIT: f32 = 10.0;
+pub(crate) const THORNS_SCORE_HIT: f32 = 50.0;
+pub(crate) const THORNS_HITBOX_HALF_SIZE: Vec2 = Vec2::new(6.0, 6.0);
+pub(crate) const SHOOTER_RANGE: f32 = 7.0;
+pub(crate) const SHOOTER_FIRE_INTERVAL: f32 = 1.5;
+pub(crate) const PROJECTILE_SPEED: f32 = 120.0;
+pub(crate) const PROJECTILE_HEALTH_HIT: f32 = 15.0;
+pub(crate) const PROJECTILE_SCORE_HIT: f32 = 75.0;
+pub(crate) const PROJECTILE_HITBOX_HALF_SIZE: Vec2 = Vec2::new(2.0, 2.0);
+pub(crate) const PROJECTILE_COLOR: Color = Color::srgb(0.9, 0.8, 0.4);
+pub(crate) const SHOVE_RANGE: f32 = 24.0;
+pub(crate) const SHOVE_DISTANCE: f32 = 12.0;
+pub(crate) const SHOVE_COOLDOWN: f32 = 4.0;
+pub(crate) const SHOVE_STUN_TIME: f32 = 3.0;
+pub(crate) const SHOVE_STUN_SCORE: f32 = 30.0;
 pub(crate) const BRONZE_SCORE: f32 = 50.0;
 pub(crate) const CARROT_HEALTH: f32 = 25.0;
 
-pub(crate) const CORRECTION: f32 = 10.0;
+pub(crate) const MAX_CACHED_PATHS: usize = 256;
+pub(crate) const SIMULATION_HZ: f64 = 64.0;
+pub(crate) const MAX_MOVE_STEP: f32 = 4.0;
+pub(crate) const PLAYER_HITBOX_HALF_SIZE: Vec2 = Vec2::new(6.0, 6.0);
+pub(crate) const CORNER_FORGIVENESS: f32 = 4.0;
+pub(crate) const PLAYER_HIT_COOLDOWN: f32 = 0.75;
+pub(crate) const PORTAL_COOLDOWN: f32 = 0.5;
+pub(crate) const TELEPORT_PHASE_DURATION: f32 = 0.25;
+pub(crate) const LEVEL_FADE_DURATION: f32 = 0.5;
+pub(crate) const PLAYER_KNOCKBACK_SPEED: f32 = 160.0;
+pub(crate) const PLAYER_KNOCKBACK_DECAY: f32 = 10.0;
+pub(crate) const CAMERA_SHAKE_DURATION: f32 = 0.2;
+pub(crate) const CAMERA_SHAKE_INTENSITY: f32 = 3.0;
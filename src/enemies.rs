@@ -1,13 +1,80 @@
 use bevy::prelude::*;
 use bevy_ecs_ldtk::{prelude::*, utils::ldtk_pixel_coords_to_translation_pivoted};
+use std::f32::consts::TAU;
+use std::time::Duration;
 
+use crate::animation::{AnimationState, SpriteAnimation};
+use crate::collisions::{Hitbox, LevelCollisions};
 use crate::consts::*;
+use crate::fields::{bool_field_or, enum_field, float_field_or, int_field_or};
+use crate::pathfinding::{Connectivity, Pathfinder};
+use crate::player::Player;
+use crate::simulation::SimulatedPosition;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub(crate) enum PatrolMode {
+    Loop,
+    #[default]
+    PingPong,
+    OneShot,
+}
+
+impl PatrolMode {
+    fn from_identifier(identifier: &str) -> Self {
+        match identifier {
+            "Loop" => PatrolMode::Loop,
+            "OneShot" => PatrolMode::OneShot,
+            _ => PatrolMode::PingPong,
+        }
+    }
+}
 
 #[derive(PartialEq, Debug, Default, Component)]
 pub(crate) struct Patrol {
     pub points: Vec<Vec2>,
     pub index: usize,
     pub forward: bool,
+    pub speed: f32,
+    pub mode: PatrolMode,
+}
+
+impl Patrol {
+    fn advance(&mut self) {
+        let last = self.points.len() - 1;
+
+        match self.mode {
+            PatrolMode::Loop => self.index = (self.index + 1) % self.points.len(),
+            PatrolMode::PingPong => {
+                if (self.forward && self.index == last) || (!self.forward && self.index == 0) {
+                    self.forward = !self.forward;
+                }
+
+                if self.forward {
+                    self.index += 1;
+                } else {
+                    self.index -= 1;
+                }
+            }
+            PatrolMode::OneShot => self.index = (self.index + 1).min(last),
+        }
+    }
+
+    fn turn_around(&mut self) {
+        let len = self.points.len();
+
+        match self.mode {
+            PatrolMode::Loop => self.index = (self.index + len - 1) % len,
+            PatrolMode::PingPong => {
+                self.forward = !self.forward;
+                self.index = if self.forward {
+                    (self.index + 1).min(len - 1)
+                } else {
+                    self.index.saturating_sub(1)
+                };
+            }
+            PatrolMode::OneShot => (),
+        }
+    }
 }
 
 impl LdtkEntity for Patrol {
@@ -32,7 +99,7 @@ impl LdtkEntity for Patrol {
             .expect("patrol field should be correclty typed");
 
         for ldtk_point in ldtk_patrol_points {
-            let pixel_coords = (ldtk_point.as_vec2() + Vec2::new(0.5, 1.))
+            let pixel_coords = (ldtk_point.as_vec2() + entity_instance.pivot)
                 * Vec2::splat(layer_instance.grid_size as f32);
             points.push(ldtk_pixel_coords_to_translation_pivoted(
                 pixel_coords.as_ivec2(),
@@ -46,6 +113,10 @@ impl LdtkEntity for Patrol {
             points,
             index: 1,
             forward: true,
+            speed: float_field_or(entity_instance, "speed", COW_SPEED),
+            mode: enum_field(entity_instance, "patrol_mode")
+                .map(PatrolMode::from_identifier)
+                .unwrap_or_default(),
         }
     }
 }
@@ -53,41 +124,564 @@ impl LdtkEntity for Patrol {
 #[derive(Default, Component)]
 pub(crate) struct Cow;
 
+#[derive(Clone, Default, Component)]
+pub(crate) struct Hostile {
+    pub(crate) damage: f32,
+    pub(crate) score: f32,
+}
+
+fn cow_hostile(entity_instance: &EntityInstance) -> Hostile {
+    Hostile {
+        damage: float_field_or(entity_instance, "damage", COW_HEALTH_HIT),
+        score: float_field_or(entity_instance, "score", COW_SCORE_HIT),
+    }
+}
+
+#[derive(Component)]
+pub(crate) struct Stunned(Timer);
+
+impl Default for Stunned {
+    fn default() -> Self {
+        Stunned(Timer::from_seconds(SHOVE_STUN_TIME, TimerMode::Once))
+    }
+}
+
+fn cow_hitbox(_: &EntityInstance) -> Hitbox {
+    Hitbox::new(COW_HITBOX_HALF_SIZE)
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub(crate) enum Behaviour {
+    #[default]
+    Patrol,
+    Alert,
+    Chase,
+    Return,
+}
+
+#[derive(Component)]
+pub(crate) struct Aggro {
+    pub(crate) behaviour: Behaviour,
+    sight_radius: f32,
+    chase_speed: f32,
+    alert_timer: Timer,
+    chase_timer: Timer,
+    repath_timer: Timer,
+    connectivity: Connectivity,
+    path: Vec<Vec2>,
+}
+
+impl Default for Aggro {
+    fn default() -> Self {
+        cow_aggro(&EntityInstance::default())
+    }
+}
+
+impl Aggro {
+    fn set_behaviour(&mut self, behaviour: Behaviour) {
+        self.behaviour = behaviour;
+        self.alert_timer.reset();
+        self.chase_timer.reset();
+        self.repath_timer.reset();
+        self.path.clear();
+    }
+}
+
+fn cow_aggro(entity_instance: &EntityInstance) -> Aggro {
+    Aggro {
+        behaviour: Behaviour::Patrol,
+        sight_radius: float_field_or(entity_instance, "sight_radius", COW_SIGHT_RADIUS)
+            * GRID_SIZE as f32,
+        chase_speed: float_field_or(entity_instance, "chase_speed", COW_CHASE_SPEED),
+        alert_timer: Timer::from_seconds(
+            float_field_or(entity_instance, "alert_time", COW_ALERT_TIME),
+            TimerMode::Once,
+        ),
+        chase_timer: Timer::from_seconds(
+            float_field_or(entity_instance, "chase_timeout", COW_CHASE_TIMEOUT),
+            TimerMode::Once,
+        ),
+        repath_timer: Timer::from_seconds(COW_REPATH_INTERVAL, TimerMode::Once),
+        connectivity: if bool_field_or(entity_instance, "diagonal", true) {
+            Connectivity::Eight
+        } else {
+            Connectivity::Four
+        },
+        path: Vec::new(),
+    }
+}
+
+fn cow_animation(_: &EntityInstance) -> SpriteAnimation {
+    SpriteAnimation::new(
+        COW_ANIMATION_FPS,
+        vec![
+            (AnimationState::Idle, COW_IDLE_FRAMES),
+            (AnimationState::Walk, COW_WALK_FRAMES),
+        ],
+    )
+}
+
 #[derive(Default, Bundle, LdtkEntity)]
 pub(crate) struct CowBundle {
     pub cow: Cow,
+    #[with(cow_hostile)]
+    pub hostile: Hostile,
+    #[with(cow_hitbox)]
+    pub hitbox: Hitbox,
+    #[with(cow_animation)]
+    pub animation: SpriteAnimation,
     #[sprite_sheet_bundle]
     pub sprite_sheet_bundle: LdtkSpriteSheetBundle,
     #[ldtk_entity]
     pub patrol: Patrol,
+    #[with(cow_aggro)]
+    pub aggro: Aggro,
+    pub simulated_position: SimulatedPosition,
+}
+
+#[cfg(feature = "dev_console")]
+impl CowBundle {
+    pub(crate) fn new(
+        sprite_sheet_bundle: LdtkSpriteSheetBundle,
+        translation: Vec3,
+        patrol_points: Vec<Vec2>,
+    ) -> Self {
+        let entity_instance = EntityInstance::default();
+        let mut points = vec![translation.truncate()];
+        points.extend(patrol_points);
+
+        CowBundle {
+            cow: Cow,
+            hostile: cow_hostile(&entity_instance),
+            hitbox: cow_hitbox(&entity_instance),
+            animation: cow_animation(&entity_instance),
+            sprite_sheet_bundle,
+            patrol: Patrol {
+                points,
+                index: 1,
+                forward: true,
+                speed: float_field_or(&entity_instance, "speed", COW_SPEED),
+                mode: PatrolMode::default(),
+            },
+            aggro: cow_aggro(&entity_instance),
+            simulated_position: SimulatedPosition {
+                previous: translation,
+                current: translation,
+            },
+        }
+    }
+}
+
+#[derive(Default, Component)]
+pub(crate) struct ChickenHouse {
+    texture: Handle<Image>,
+    layout: Handle<TextureAtlasLayout>,
+    spawn_timer: Timer,
+    max_chickens: usize,
+    speed: f32,
+    hostile: Hostile,
+}
+
+impl LdtkEntity for ChickenHouse {
+    fn bundle_entity(
+        entity_instance: &EntityInstance,
+        _: &LayerInstance,
+        _: Option<&Handle<Image>>,
+        _: Option<&TilesetDefinition>,
+        asset_server: &AssetServer,
+        texture_atlases: &mut Assets<TextureAtlasLayout>,
+    ) -> ChickenHouse {
+        ChickenHouse {
+            texture: asset_server.load(CHICKEN_SPRITE_PATH),
+            layout: texture_atlases.add(TextureAtlasLayout::from_grid(
+                UVec2::splat(CHICKEN_SPRITE_SIZE),
+                CHICKEN_SPRITE_COLUMNS,
+                CHICKEN_SPRITE_ROWS,
+                None,
+                None,
+            )),
+            spawn_timer: Timer::from_seconds(
+                float_field_or(entity_instance, "spawn_interval", CHICKEN_SPAWN_INTERVAL),
+                TimerMode::Repeating,
+            ),
+            max_chickens: int_field_or(entity_instance, "max_chickens", MAX_CHICKENS_PER_HOUSE),
+            speed: float_field_or(entity_instance, "speed", CHICKEN_SPEED),
+            hostile: Hostile {
+                damage: float_field_or(entity_instance, "damage", CHICKEN_HEALTH_HIT),
+                score: float_field_or(entity_instance, "score", CHICKEN_SCORE_HIT),
+            },
+        }
+    }
+}
+
+#[derive(Default, Bundle, LdtkEntity)]
+pub(crate) struct ChickenHouseBundle {
+    #[ldtk_entity]
+    pub chicken_house: ChickenHouse,
+    #[sprite_sheet_bundle]
+    pub sprite_sheet_bundle: LdtkSpriteSheetBundle,
+}
+
+#[derive(Component)]
+pub(crate) struct Chicken {
+    house: Entity,
+    direction: Vec2,
+    speed: f32,
+    wander_timer: Timer,
 }
 
-pub(crate) fn patrol(mut query: Query<(&mut Transform, &mut Patrol)>, time: Res<Time>) {
-    for (mut transform, mut patrol) in &mut query {
-        if patrol.points.len() <= 1 {
+impl Chicken {
+    fn new(house: Entity, speed: f32) -> Self {
+        let mut chicken = Chicken {
+            house,
+            direction: Vec2::ZERO,
+            speed,
+            wander_timer: Timer::from_seconds(CHICKEN_WANDER_MAX, TimerMode::Once),
+        };
+        chicken.change_direction();
+        chicken
+    }
+
+    fn change_direction(&mut self) {
+        self.direction = Vec2::from_angle(fastrand::f32() * TAU);
+        self.wander_timer.set_duration(Duration::from_secs_f32(
+            CHICKEN_WANDER_MIN.lerp(CHICKEN_WANDER_MAX, fastrand::f32()),
+        ));
+        self.wander_timer.reset();
+    }
+}
+
+fn chicken_animation() -> SpriteAnimation {
+    SpriteAnimation::new(
+        CHICKEN_ANIMATION_FPS,
+        vec![
+            (AnimationState::Idle, CHICKEN_IDLE_FRAMES),
+            (AnimationState::Walk, CHICKEN_WALK_FRAMES),
+        ],
+    )
+}
+
+fn grid_coords(translation: Vec2) -> GridCoords {
+    bevy_ecs_ldtk::utils::translation_to_grid_coords(translation, IVec2::splat(GRID_SIZE))
+}
+
+fn path_to(
+    pathfinder: &mut Pathfinder,
+    level_collisions: &LevelCollisions,
+    from: Vec2,
+    to: Vec2,
+    connectivity: Connectivity,
+) -> Vec<Vec2> {
+    pathfinder
+        .path(
+            level_collisions,
+            grid_coords(from),
+            grid_coords(to),
+            connectivity,
+        )
+        .map(|path| {
+            path.into_iter()
+                .skip(1)
+                .map(|cell| {
+                    bevy_ecs_ldtk::utils::grid_coords_to_translation(cell, IVec2::splat(GRID_SIZE))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Movement {
+    Moving,
+    Arrived,
+    Blocked,
+}
+
+fn move_towards(
+    position: &mut SimulatedPosition,
+    hitbox: &Hitbox,
+    level_collisions: &LevelCollisions,
+    target: Vec2,
+    step: f32,
+) -> Movement {
+    let current = position.current.truncate();
+    let distance = current.distance(target);
+    let delta = if distance <= step {
+        target - current
+    } else {
+        (target - current).normalize() * step
+    };
+
+    let moved = level_collisions.move_and_slide(current, hitbox.half_size, delta);
+    position.current = moved.extend(position.current.z);
+
+    if moved.distance(target) <= f32::EPSILON {
+        Movement::Arrived
+    } else if distance - moved.distance(target) < delta.length() * COW_BLOCKED_PROGRESS {
+        Movement::Blocked
+    } else {
+        Movement::Moving
+    }
+}
+
+pub(crate) fn update_behaviour(
+    mut query: Query<
+        (&SimulatedPosition, &Patrol, &mut Aggro),
+        (Without<Player>, Without<Stunned>),
+    >,
+    player: Query<&SimulatedPosition, With<Player>>,
+    level_collisions: Res<LevelCollisions>,
+    mut pathfinder: ResMut<Pathfinder>,
+    time: Res<Time>,
+) {
+    let player_position = player
+        .get_single()
+        .ok()
+        .map(|position| position.current.truncate());
+
+    for (position, patrol, mut aggro) in &mut query {
+        let position = position.current.truncate();
+        let sees_player = player_position.is_some_and(|player_position| {
+            position.distance(player_position) <= aggro.sight_radius
+                && level_collisions.line_of_sight(position, player_position)
+        });
+
+        match aggro.behaviour {
+            Behaviour::Patrol | Behaviour::Return if sees_player => {
+                aggro.set_behaviour(Behaviour::Alert);
+            }
+            Behaviour::Patrol => (),
+            Behaviour::Alert => {
+                aggro.alert_timer.tick(time.delta());
+
+                if !sees_player {
+                    aggro.set_behaviour(Behaviour::Return);
+                } else if aggro.alert_timer.finished() {
+                    aggro.set_behaviour(Behaviour::Chase);
+                }
+            }
+            Behaviour::Chase => {
+                if sees_player {
+                    aggro.chase_timer.reset();
+                } else {
+                    aggro.chase_timer.tick(time.delta());
+                }
+
+                if aggro.chase_timer.finished() {
+                    aggro.set_behaviour(Behaviour::Return);
+                    continue;
+                }
+
+                aggro.repath_timer.tick(time.delta());
+
+                if let Some(player_position) = player_position {
+                    if (aggro.path.is_empty() || aggro.repath_timer.finished())
+                        && grid_coords(position) != grid_coords(player_position)
+                    {
+                        aggro.path = path_to(
+                            &mut pathfinder,
+                            &level_collisions,
+                            position,
+                            player_position,
+                            aggro.connectivity,
+                        );
+                        aggro.repath_timer.reset();
+
+                        if aggro.path.is_empty() {
+                            aggro.set_behaviour(Behaviour::Return);
+                        }
+                    }
+                }
+            }
+            Behaviour::Return => {
+                let Some(home) = patrol.points.get(patrol.index) else {
+                    aggro.set_behaviour(Behaviour::Patrol);
+                    continue;
+                };
+
+                if grid_coords(position) == grid_coords(*home) {
+                    aggro.set_behaviour(Behaviour::Patrol);
+                } else if aggro.path.is_empty() {
+                    aggro.path = path_to(
+                        &mut pathfinder,
+                        &level_collisions,
+                        position,
+                        *home,
+                        aggro.connectivity,
+                    );
+
+                    if aggro.path.is_empty() {
+                        aggro.set_behaviour(Behaviour::Patrol);
+                    }
+                }
+            }
+        }
+    }
+}
+
+pub(crate) fn chase(
+    mut query: Query<(&mut SimulatedPosition, &Hitbox, &Patrol, &mut Aggro), Without<Stunned>>,
+    level_collisions: Res<LevelCollisions>,
+    time: Res<Time>,
+) {
+    for (mut position, hitbox, patrol, mut aggro) in &mut query {
+        let speed = match aggro.behaviour {
+            Behaviour::Chase => aggro.chase_speed,
+            Behaviour::Return => patrol.speed,
+            Behaviour::Patrol | Behaviour::Alert => continue,
+        };
+
+        if let Some(waypoint) = aggro.path.first().copied() {
+            match move_towards(
+                &mut position,
+                hitbox,
+                &level_collisions,
+                waypoint,
+                speed * time.delta_seconds(),
+            ) {
+                Movement::Arrived => {
+                    aggro.path.remove(0);
+                }
+                Movement::Blocked => {
+                    let cell_centre = bevy_ecs_ldtk::utils::grid_coords_to_translation(
+                        grid_coords(position.current.truncate()),
+                        IVec2::splat(GRID_SIZE),
+                    );
+
+                    if waypoint != cell_centre {
+                        aggro.path.insert(0, cell_centre);
+                    } else if aggro.behaviour == Behaviour::Chase {
+                        aggro.set_behaviour(Behaviour::Return);
+                    } else {
+                        aggro.set_behaviour(Behaviour::Patrol);
+                    }
+                }
+                Movement::Moving => (),
+            }
+        }
+    }
+}
+
+pub(crate) fn patrol(
+    mut query: Query<
+        (&mut SimulatedPosition, &Hitbox, &mut Patrol, Option<&Aggro>),
+        Without<Stunned>,
+    >,
+    level_collisions: Res<LevelCollisions>,
+    time: Res<Time>,
+) {
+    for (mut position, hitbox, mut patrol, aggro) in &mut query {
+        if patrol.points.len() <= 1
+            || aggro.is_some_and(|aggro| aggro.behaviour != Behaviour::Patrol)
+        {
+            continue;
+        }
+
+        let target = patrol.points[patrol.index];
+        let step = patrol.speed * time.delta_seconds();
+
+        match move_towards(&mut position, hitbox, &level_collisions, target, step) {
+            Movement::Arrived => patrol.advance(),
+            Movement::Blocked => patrol.turn_around(),
+            Movement::Moving => (),
+        }
+    }
+}
+
+pub(crate) fn spawn_chickens(
+    mut houses: Query<(Entity, &Transform, &Parent, &mut ChickenHouse)>,
+    chickens: Query<&Chicken>,
+    mut commands: Commands,
+    time: Res<Time>,
+) {
+    for (house, transform, parent, mut chicken_house) in &mut houses {
+        chicken_house.spawn_timer.tick(time.delta());
+
+        if !chicken_house.spawn_timer.just_finished()
+            || chickens
+                .iter()
+                .filter(|chicken| chicken.house == house)
+                .count()
+                >= chicken_house.max_chickens
+        {
             continue;
         }
 
-        let start = patrol
-            .points
-            .first()
-            .unwrap_or(&Vec2::new(0.0, 0.0))
-            .extend(0.0);
-        let mut finish = patrol
-            .points
-            .last()
-            .unwrap_or(&Vec2::new(0.0, 0.0))
-            .extend(0.0);
-        finish.y += GRID_SIZE as f32 / 2.0;
-        let direction = (finish - start).normalize();
-        let orientation = if patrol.forward { 1.0 } else { -1.0 };
-
-        if (transform.translation.x >= finish.x && patrol.forward)
-            || (transform.translation.x <= start.x && !patrol.forward)
+        let translation = transform.translation - Vec3::Y * GRID_SIZE as f32;
+        commands
+            .spawn((
+                SpriteBundle {
+                    texture: chicken_house.texture.clone(),
+                    transform: Transform::from_translation(translation),
+                    ..default()
+                },
+                TextureAtlas::from(chicken_house.layout.clone()),
+                chicken_animation(),
+                chicken_house.hostile.clone(),
+                Hitbox::new(CHICKEN_HITBOX_HALF_SIZE),
+                Chicken::new(house, chicken_house.speed),
+                SimulatedPosition {
+                    previous: translation,
+                    current: translation,
+                },
+            ))
+            .set_parent(parent.get());
+    }
+}
+
+pub(crate) fn wander(
+    mut query: Query<(&mut SimulatedPosition, &Hitbox, &mut Chicken), Without<Stunned>>,
+    level_collisions: Res<LevelCollisions>,
+    time: Res<Time>,
+) {
+    for (mut position, hitbox, mut chicken) in &mut query {
+        chicken.wander_timer.tick(time.delta());
+
+        let current = position.current.truncate();
+        let step = chicken.speed * time.delta_seconds();
+        let moved =
+            level_collisions.move_and_slide(current, hitbox.half_size, chicken.direction * step);
+        position.current = moved.extend(position.current.z);
+
+        if chicken.wander_timer.finished() || moved.distance(current) < step * COW_BLOCKED_PROGRESS
         {
-            patrol.forward = !patrol.forward;
+            chicken.change_direction();
+        }
+    }
+}
+
+pub(crate) fn recover_from_stuns(
+    mut query: Query<(Entity, &mut Stunned, &mut Sprite)>,
+    mut commands: Commands,
+    time: Res<Time>,
+) {
+    for (entity, mut stunned, mut sprite) in &mut query {
+        stunned.0.tick(time.delta());
+
+        if stunned.0.finished() {
+            sprite.color = Color::WHITE;
+            commands.entity(entity).remove::<Stunned>();
+        }
+    }
+}
+
+pub(crate) fn animate_enemies(
+    mut query: Query<
+        (&SimulatedPosition, &mut Sprite, &mut SpriteAnimation),
+        Or<(With<Cow>, With<Chicken>)>,
+    >,
+) {
+    for (position, mut sprite, mut animation) in &mut query {
+        let direction = position.current - position.previous;
+
+        if direction.length() > f32::EPSILON {
+            animation.state = AnimationState::Walk;
+        } else {
+            animation.state = AnimationState::Idle;
         }
 
-        transform.translation += orientation * direction * COW_SPEED * time.delta_seconds();
+        if direction.x.abs() > f32::EPSILON {
+            sprite.flip_x = direction.x < 0.0;
+        }
     }
 }
@@ -0,0 +1,64 @@
+use bevy_ecs_ldtk::prelude::*;
+
+pub(crate) fn float_field_or(
+    entity_instance: &EntityInstance,
+    identifier: &str,
+    default: f32,
+) -> f32 {
+    match entity_instance.get_field(identifier) {
+        Ok(FieldValue::Float(Some(value))) => *value,
+        Ok(FieldValue::Int(Some(value))) => *value as f32,
+        _ => default,
+    }
+}
+
+pub(crate) fn enum_field<'a>(
+    entity_instance: &'a EntityInstance,
+    identifier: &str,
+) -> Option<&'a str> {
+    match entity_instance.get_field(identifier) {
+        Ok(FieldValue::Enum(Some(value))) => Some(value),
+        _ => None,
+    }
+}
+
+pub(crate) fn bool_field_or(
+    entity_instance: &EntityInstance,
+    identifier: &str,
+    default: bool,
+) -> bool {
+    match entity_instance.get_field(identifier) {
+        Ok(FieldValue::Bool(value)) => *value,
+        _ => default,
+    }
+}
+
+pub(crate) fn entity_ref_field(
+    entity_instance: &EntityInstance,
+    identifier: &str,
+) -> Option<EntityIid> {
+    match entity_instance.get_field(identifier) {
+        Ok(FieldValue::EntityRef(Some(entity_ref))) => {
+            Some(EntityIid::new(entity_ref.entity_iid.clone()))
+        }
+        _ => None,
+    }
+}
+
+pub(crate) fn string_field<'a>(fields: &'a impl LdtkFields, identifier: &str) -> Option<&'a str> {
+    match fields.get_field(identifier) {
+        Ok(FieldValue::String(Some(value))) => Some(value),
+        _ => None,
+    }
+}
+
+pub(crate) fn int_field_or(
+    entity_instance: &EntityInstance,
+    identifier: &str,
+    default: usize,
+) -> usize {
+    match entity_instance.get_field(identifier) {
+        Ok(FieldValue::Int(Some(value))) => (*value).max(0) as usize,
+        _ => default,
+    }
+}
@@ -1,8 +1,15 @@
-use bevy::prelude::*;
+use bevy::{prelude::*, utils::HashSet};
 use bevy_ecs_ldtk::prelude::*;
 
+use crate::animation::{AnimationState, SpriteAnimation};
 use crate::consts::*;
-use crate::player::{Player, PlayerStats};
+use crate::fields::{bool_field_or, entity_ref_field};
+use crate::highscores::HighScores;
+use crate::input::{Action, ActionState};
+use crate::items::{Pickup, PickupEffect};
+use crate::levels::{LdtkProjectHandle, LevelProgress, LevelStats, LevelSummary};
+use crate::player::{finished_timer, Player, PlayerStats};
+use crate::teleport::Teleport;
 
 #[derive(States, Debug, Clone, PartialEq, Eq, Hash)]
 pub(crate) enum GameState {
@@ -10,7 +17,13 @@ pub(crate) enum GameState {
     PauseMenu,
     Running,
     Teleporting,
+    LevelComplete,
+    Victory,
+    LevelSelect,
+    NameEntry,
     GameOver,
+    HighScores,
+    Controls,
 }
 
 #[derive(Default, Component)]
@@ -26,10 +39,21 @@ pub(crate) struct GoalBundle {
 }
 
 #[derive(Default, Component)]
-pub(crate) struct PortalEntry;
+pub(crate) struct PortalEntry {
+    exit: Option<EntityIid>,
+    two_way: bool,
+}
+
+fn portal_entry(entity_instance: &EntityInstance) -> PortalEntry {
+    PortalEntry {
+        exit: entity_ref_field(entity_instance, "exit"),
+        two_way: bool_field_or(entity_instance, "two_way", false),
+    }
+}
 
 #[derive(Default, Bundle, LdtkEntity)]
 pub(crate) struct PortalEntryBundle {
+    #[with(portal_entry)]
     portal_entry: PortalEntry,
     #[sprite_sheet_bundle]
     sprite_bundle: LdtkSpriteSheetBundle,
@@ -49,14 +73,66 @@ pub(crate) struct PortalExitBundle {
     grid_coords: GridCoords,
 }
 
+#[derive(Component)]
+pub(crate) struct PortalCooldown {
+    arrived_at: Option<Entity>,
+    timer: Timer,
+}
+
+impl PortalCooldown {
+    pub(crate) fn arrive(&mut self, portal: Entity) {
+        self.arrived_at = Some(portal);
+        self.timer.reset();
+    }
+}
+
+impl Default for PortalCooldown {
+    fn default() -> Self {
+        PortalCooldown {
+            arrived_at: None,
+            timer: finished_timer(PORTAL_COOLDOWN),
+        }
+    }
+}
+
 #[derive(Default, Component)]
 pub(crate) struct BackgroundMusic;
 
+#[derive(Default, Resource)]
+pub(crate) struct GameOverStats {
+    pub(crate) score: f32,
+    pub(crate) level: usize,
+    pub(crate) keys_collected: usize,
+    pub(crate) time_played: f32,
+    pub(crate) victory: bool,
+}
+
+impl GameOverStats {
+    fn new(player_stats: &PlayerStats, level: usize, victory: bool) -> Self {
+        GameOverStats {
+            score: player_stats.score,
+            level,
+            keys_collected: player_stats.keys_collected,
+            time_played: player_stats.time_played.elapsed_secs(),
+            victory,
+        }
+    }
+}
+
 pub(crate) fn check_goal(
     players: Query<&GridCoords, (With<Player>, Changed<GridCoords>)>,
     goals: Query<&GridCoords, With<Goal>>,
-    mut player_stats: ResMut<PlayerStats>,
-    level_selection: ResMut<LevelSelection>,
+    pickups: Query<&Pickup>,
+    player_stats: Res<PlayerStats>,
+    level_stats: Res<LevelStats>,
+    mut level_summary: ResMut<LevelSummary>,
+    level_selection: Res<LevelSelection>,
+    ldtk_project_handle: Res<LdtkProjectHandle>,
+    ldtk_project_assets: Res<Assets<LdtkProject>>,
+    mut level_progress: ResMut<LevelProgress>,
+    mut game_over_stats: ResMut<GameOverStats>,
+    high_scores: Res<HighScores>,
+    mut next_state: ResMut<NextState<GameState>>,
     mut commands: Commands,
     asset_server: Res<AssetServer>,
 ) {
@@ -71,9 +147,41 @@ pub(crate) fn check_goal(
         };
 
         if player_stats.keys == indices.level + 1 {
-            player_stats.keys = 0;
-            player_stats.health = PLAYER_MAX_HEALTH;
-            indices.level += 1;
+            let Some(levels) = ldtk_project_handle.levels(&ldtk_project_assets) else {
+                return;
+            };
+
+            if let Some(level) = levels.get(indices.level) {
+                level_progress.record(indices.level, level, &level_stats);
+            }
+
+            if indices.level + 1 >= levels.len() {
+                *game_over_stats = GameOverStats::new(&player_stats, indices.level + 1, true);
+
+                if high_scores.qualifies(player_stats.score) {
+                    next_state.set(GameState::NameEntry);
+                } else {
+                    next_state.set(GameState::Victory);
+                }
+
+                commands.spawn(AudioBundle {
+                    source: asset_server.load(LEVEL_SOUND_PATH),
+                    ..default()
+                });
+                return;
+            }
+
+            let bronze_remaining = pickups
+                .iter()
+                .filter(|pickup| matches!(pickup.effect, PickupEffect::Score(_)))
+                .count();
+            *level_summary = LevelSummary::new(
+                &level_stats,
+                &player_stats,
+                indices.level + 1,
+                bronze_remaining,
+            );
+            next_state.set(GameState::LevelComplete);
             commands.spawn(AudioBundle {
                 source: asset_server.load(LEVEL_SOUND_PATH),
                 ..default()
@@ -83,61 +191,98 @@ pub(crate) fn check_goal(
 }
 
 pub(crate) fn check_portal_entry(
-    mut player_transform: Query<&mut Transform, With<Player>>,
-    mut player_grid_pos: Query<&mut GridCoords, With<Player>>,
-    mut player_atlas: Query<&mut TextureAtlas, With<Player>>,
-    portal_entries: Query<&GridCoords, (With<PortalEntry>, Without<Player>)>,
-    portal_exits: Query<&GridCoords, (With<PortalExit>, Without<Player>)>,
-    asset_server: Res<AssetServer>,
-    mut commands: Commands,
+    mut player: Query<(&GridCoords, &mut SpriteAnimation, &mut PortalCooldown), With<Player>>,
+    portal_entries: Query<(Entity, &GridCoords, &PortalEntry), Without<Player>>,
+    portal_exits: Query<(Entity, &GridCoords, &EntityIid), (With<PortalExit>, Without<Player>)>,
+    mut teleport: ResMut<Teleport>,
     mut next_state: ResMut<NextState<GameState>>,
-    mut player_stats: ResMut<PlayerStats>,
+    mut unlinked_entries: Local<HashSet<Entity>>,
     time: Res<Time>,
 ) {
-    for mut player_grid_pos in &mut player_grid_pos {
-        if let Ok(portal_entry_grid_pos) = portal_entries.get_single() {
-            if let Ok(portal_exit_grid_pos) = portal_exits.get_single() {
-                if *player_grid_pos == *portal_entry_grid_pos {
-                    if let Ok(mut player_tranform) = player_transform.get_single_mut() {
-                        player_tranform.translation =
-                            bevy_ecs_ldtk::utils::grid_coords_to_translation(
-                                *portal_exit_grid_pos,
-                                IVec2::new(GRID_SIZE, GRID_SIZE),
-                            )
-                            .extend(0.0);
-                    }
+    let Ok((player_grid_pos, mut player_animation, mut portal_cooldown)) = player.get_single_mut()
+    else {
+        return;
+    };
 
-                    if let Ok(mut player_atlas) = player_atlas.get_single_mut() {
-                        player_atlas.index = PLAYER_ATLAS_INDEX_DOWN;
-                    }
+    portal_cooldown.timer.tick(time.delta());
 
-                    next_state.set(GameState::Teleporting);
-                    player_stats.teleport_timer.tick(time.delta());
-
-                    if player_stats.teleport_timer.finished() {
-                        commands.spawn(AudioBundle {
-                            source: asset_server.load(TELEPORT_SOUND_PATH),
-                            ..default()
-                        });
-                        *player_grid_pos = *portal_exit_grid_pos;
-                        player_stats.teleport_timer.reset();
-                        next_state.set(GameState::Running);
-                    }
+    let mut links = Vec::new();
+    for (entry, entry_grid_pos, portal_entry) in &portal_entries {
+        let exit = match &portal_entry.exit {
+            Some(exit_iid) => portal_exits.iter().find(|(_, _, iid)| *iid == exit_iid),
+            None => {
+                let exit = portal_exits.get_single().ok();
+
+                if exit.is_none() && unlinked_entries.insert(entry) {
+                    warn!(
+                        "portal entry {:?} has no exit set and the level has {} exits",
+                        entry,
+                        portal_exits.iter().count()
+                    );
                 }
+
+                exit
+            }
+        };
+
+        if let Some((exit, exit_grid_pos, _)) = exit {
+            links.push((entry, *entry_grid_pos, exit, *exit_grid_pos));
+
+            if portal_entry.two_way {
+                links.push((exit, *exit_grid_pos, entry, *entry_grid_pos));
             }
         }
     }
+
+    if let Some(arrived_at) = portal_cooldown.arrived_at {
+        let still_on_arrival = links
+            .iter()
+            .any(|(portal, grid_pos, ..)| *portal == arrived_at && *grid_pos == *player_grid_pos);
+
+        if !still_on_arrival {
+            portal_cooldown.arrived_at = None;
+        }
+    }
+
+    if !portal_cooldown.timer.finished() {
+        return;
+    }
+
+    let Some((_, _, destination, destination_grid_pos)) =
+        links.into_iter().find(|(portal, grid_pos, ..)| {
+            *grid_pos == *player_grid_pos && portal_cooldown.arrived_at != Some(*portal)
+        })
+    else {
+        return;
+    };
+
+    player_animation.state = AnimationState::IdleDown;
+    teleport.start(destination, destination_grid_pos);
+    next_state.set(GameState::Teleporting);
 }
 
 pub(crate) fn check_game_over(
     player_state: Res<PlayerStats>,
+    level_selection: Res<LevelSelection>,
+    mut game_over_stats: ResMut<GameOverStats>,
+    high_scores: Res<HighScores>,
     mut next_state: ResMut<NextState<GameState>>,
     audio_sink: Query<&AudioSink, With<BackgroundMusic>>,
     mut commands: Commands,
     asset_server: Res<AssetServer>,
 ) {
     if player_state.health <= 0.0 {
-        next_state.set(GameState::GameOver);
+        let level = match level_selection.into_inner() {
+            LevelSelection::Indices(indices) => indices.level + 1,
+            _ => 1,
+        };
+        *game_over_stats = GameOverStats::new(&player_state, level, false);
+
+        if high_scores.qualifies(player_state.score) {
+            next_state.set(GameState::NameEntry);
+        } else {
+            next_state.set(GameState::GameOver);
+        }
 
         if let Ok(audio_sink) = audio_sink.get_single() {
             if !audio_sink.is_paused() {
@@ -153,12 +298,12 @@ pub(crate) fn check_game_over(
 }
 
 pub(crate) fn toggle_state(
-    input: Res<ButtonInput<KeyCode>>,
+    action_state: Res<ActionState>,
     game_state: ResMut<State<GameState>>,
     mut next_state: ResMut<NextState<GameState>>,
     audio_sink: Query<&AudioSink, With<BackgroundMusic>>,
 ) {
-    if input.just_released(KeyCode::KeyP) || input.just_released(KeyCode::Space) {
+    if action_state.just_released(Action::Pause) {
         if let Ok(audio_sink) = audio_sink.get_single() {
             match game_state.get() {
                 GameState::Menu => {
@@ -186,25 +331,60 @@ pub(crate) fn toggle_state(
 }
 
 pub(crate) fn toggle_music(
-    input: Res<ButtonInput<KeyCode>>,
+    action_state: Res<ActionState>,
     audio_sink: Query<&AudioSink, With<BackgroundMusic>>,
 ) {
-    if input.just_pressed(KeyCode::KeyM) {
+    if action_state.just_pressed(Action::ToggleMusic) {
         if let Ok(audio_sink) = audio_sink.get_single() {
             audio_sink.toggle();
         }
     }
 }
 
-pub(crate) fn check_cheats(
-    input: Res<ButtonInput<KeyCode>>,
-    level_selection: ResMut<LevelSelection>,
+pub(crate) fn restart_game(
+    mut player_stats: ResMut<PlayerStats>,
+    mut level_selection: ResMut<LevelSelection>,
+    mut next_state: ResMut<NextState<GameState>>,
+    audio_sink: Query<&AudioSink, With<BackgroundMusic>>,
 ) {
-    if input.just_released(KeyCode::ControlRight) && input.just_released(KeyCode::KeyL) {
-        let indices = match level_selection.into_inner() {
-            LevelSelection::Indices(indices) => indices,
-            _ => panic!("level selection should always be Indices in this game"),
-        };
-        indices.level += 1;
+    *player_stats = PlayerStats::default();
+    *level_selection = LevelSelection::index(0);
+    next_state.set(GameState::Running);
+
+    if let Ok(audio_sink) = audio_sink.get_single() {
+        if audio_sink.is_paused() {
+            audio_sink.play();
+        }
+    }
+}
+
+pub(crate) fn retry_level(
+    mut player_stats: ResMut<PlayerStats>,
+    mut next_state: ResMut<NextState<GameState>>,
+    audio_sink: Query<&AudioSink, With<BackgroundMusic>>,
+) {
+    *player_stats = player_stats.retry_level();
+    next_state.set(GameState::Running);
+
+    if let Ok(audio_sink) = audio_sink.get_single() {
+        if audio_sink.is_paused() {
+            audio_sink.play();
+        }
+    }
+}
+
+pub(crate) fn return_to_menu(
+    mut player_stats: ResMut<PlayerStats>,
+    mut level_selection: ResMut<LevelSelection>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    *player_stats = PlayerStats::default();
+    *level_selection = LevelSelection::index(0);
+    next_state.set(GameState::Menu);
+}
+
+pub(crate) fn quit_game(action_state: Res<ActionState>, mut app_exit: EventWriter<AppExit>) {
+    if action_state.just_pressed(Action::Quit) {
+        app_exit.send(AppExit::Success);
     }
 }
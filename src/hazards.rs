@@ -0,0 +1,163 @@
+use bevy::prelude::*;
+use bevy_ecs_ldtk::prelude::*;
+
+use crate::collisions::{Hitbox, LevelCollisions};
+use crate::consts::*;
+use crate::enemies::{Hostile, Stunned};
+use crate::fields::float_field_or;
+use crate::player::Player;
+use crate::simulation::SimulatedPosition;
+
+fn thorns_hostile(entity_instance: &EntityInstance) -> Hostile {
+    Hostile {
+        damage: float_field_or(entity_instance, "damage", THORNS_HEALTH_HIT),
+        score: float_field_or(entity_instance, "score", THORNS_SCORE_HIT),
+    }
+}
+
+fn thorns_hitbox(_: &EntityInstance) -> Hitbox {
+    Hitbox::new(THORNS_HITBOX_HALF_SIZE)
+}
+
+#[derive(Default, Bundle, LdtkEntity)]
+pub(crate) struct ThornsBundle {
+    #[with(thorns_hostile)]
+    pub hostile: Hostile,
+    #[with(thorns_hitbox)]
+    pub hitbox: Hitbox,
+    #[sprite_sheet_bundle]
+    pub sprite_sheet_bundle: LdtkSpriteSheetBundle,
+    pub simulated_position: SimulatedPosition,
+}
+
+#[derive(Component)]
+pub(crate) struct Shooter {
+    range: f32,
+    projectile_speed: f32,
+    fire_timer: Timer,
+    hostile: Hostile,
+}
+
+impl Default for Shooter {
+    fn default() -> Self {
+        shooter(&EntityInstance::default())
+    }
+}
+
+fn shooter(entity_instance: &EntityInstance) -> Shooter {
+    Shooter {
+        range: float_field_or(entity_instance, "range", SHOOTER_RANGE) * GRID_SIZE as f32,
+        projectile_speed: float_field_or(entity_instance, "projectile_speed", PROJECTILE_SPEED),
+        fire_timer: Timer::from_seconds(
+            float_field_or(entity_instance, "fire_interval", SHOOTER_FIRE_INTERVAL),
+            TimerMode::Once,
+        ),
+        hostile: Hostile {
+            damage: float_field_or(entity_instance, "damage", PROJECTILE_HEALTH_HIT),
+            score: float_field_or(entity_instance, "score", PROJECTILE_SCORE_HIT),
+        },
+    }
+}
+
+#[derive(Default, Bundle, LdtkEntity)]
+pub(crate) struct ShooterBundle {
+    #[with(shooter)]
+    pub shooter: Shooter,
+    #[sprite_sheet_bundle]
+    pub sprite_sheet_bundle: LdtkSpriteSheetBundle,
+    pub simulated_position: SimulatedPosition,
+}
+
+#[derive(Component)]
+pub(crate) struct Projectile {
+    velocity: Vec2,
+}
+
+pub(crate) fn fire_projectiles(
+    mut shooters: Query<
+        (&SimulatedPosition, &Parent, &mut Shooter),
+        (Without<Player>, Without<Stunned>),
+    >,
+    player: Query<&SimulatedPosition, With<Player>>,
+    level_collisions: Res<LevelCollisions>,
+    mut commands: Commands,
+    time: Res<Time>,
+) {
+    let Ok(player_position) = player.get_single() else {
+        return;
+    };
+    let target = player_position.current.truncate();
+
+    for (position, parent, mut shooter) in &mut shooters {
+        shooter.fire_timer.tick(time.delta());
+
+        let origin = position.current.truncate();
+        if !shooter.fire_timer.finished()
+            || origin.distance(target) > shooter.range
+            || !level_collisions.line_of_sight(origin, target)
+        {
+            continue;
+        }
+
+        shooter.fire_timer.reset();
+
+        let translation = position.current + Vec3::Z;
+        commands
+            .spawn((
+                SpriteBundle {
+                    sprite: Sprite {
+                        color: PROJECTILE_COLOR,
+                        custom_size: Some(PROJECTILE_HITBOX_HALF_SIZE * 2.0),
+                        ..default()
+                    },
+                    transform: Transform::from_translation(translation),
+                    ..default()
+                },
+                Projectile {
+                    velocity: (target - origin).normalize_or_zero() * shooter.projectile_speed,
+                },
+                shooter.hostile.clone(),
+                Hitbox::new(PROJECTILE_HITBOX_HALF_SIZE),
+                SimulatedPosition {
+                    previous: translation,
+                    current: translation,
+                },
+            ))
+            .set_parent(parent.get());
+    }
+}
+
+pub(crate) fn move_projectiles(
+    mut projectiles: Query<(Entity, &mut SimulatedPosition, &Projectile)>,
+    level_collisions: Res<LevelCollisions>,
+    mut commands: Commands,
+    time: Res<Time>,
+) {
+    for (entity, mut position, projectile) in &mut projectiles {
+        let from = position.current.truncate();
+        let to = from + projectile.velocity * time.delta_seconds();
+
+        if level_collisions.line_of_sight(from, to) {
+            position.current = to.extend(position.current.z);
+        } else {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+pub(crate) fn despawn_projectile_hits(
+    projectiles: Query<(Entity, &SimulatedPosition, &Hitbox), With<Projectile>>,
+    player: Query<(&SimulatedPosition, &Hitbox), With<Player>>,
+    mut commands: Commands,
+) {
+    let Ok((player_position, player_hitbox)) = player.get_single() else {
+        return;
+    };
+    let player_position = player_position.current.truncate();
+
+    for (entity, position, hitbox) in &projectiles {
+        if player_hitbox.overlaps(player_position, hitbox, position.current.truncate()) {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
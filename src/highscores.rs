@@ -0,0 +1,298 @@
+use bevy::{
+    ecs::event::ManualEventReader,
+    input::{
+        keyboard::{Key, KeyboardInput},
+        ButtonState,
+    },
+    prelude::*,
+    utils::SystemTime,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::consts::*;
+use crate::gameplay::{GameOverStats, GameState};
+use crate::input::{Action, ActionState};
+use crate::storage;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) struct HighScore {
+    pub(crate) name: String,
+    pub(crate) score: f32,
+    pub(crate) level: usize,
+    pub(crate) time_played: f32,
+    pub(crate) date: u64,
+}
+
+#[derive(Default, Resource, Serialize, Deserialize)]
+pub(crate) struct HighScores {
+    pub(crate) entries: Vec<HighScore>,
+}
+
+impl HighScores {
+    pub(crate) fn load() -> Self {
+        storage::read(HIGH_SCORES_FILE_NAME)
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    fn store(&self) {
+        match serde_json::to_string(self) {
+            Ok(contents) => storage::write(HIGH_SCORES_FILE_NAME, &contents),
+            Err(err) => warn!("failed to serialize high scores: {}", err),
+        }
+    }
+
+    pub(crate) fn qualifies(&self, score: f32) -> bool {
+        self.entries.len() < MAX_HIGH_SCORES
+            || self
+                .entries
+                .last()
+                .is_some_and(|lowest| score > lowest.score)
+    }
+
+    pub(crate) fn insert(&mut self, high_score: HighScore) -> Option<usize> {
+        if !self.qualifies(high_score.score) {
+            return None;
+        }
+
+        let rank = self
+            .entries
+            .iter()
+            .position(|entry| high_score.score > entry.score)
+            .unwrap_or(self.entries.len());
+        self.entries.insert(rank, high_score);
+        self.entries.truncate(MAX_HIGH_SCORES);
+
+        Some(rank)
+    }
+}
+
+#[derive(Default, Resource)]
+pub(crate) struct PlayerName(pub(crate) String);
+
+#[derive(Default, Resource)]
+pub(crate) struct NameEntryInput(ManualEventReader<KeyboardInput>);
+
+pub(crate) fn clear_name_entry_input(
+    mut name_entry_input: ResMut<NameEntryInput>,
+    keyboard_events: Res<Events<KeyboardInput>>,
+) {
+    name_entry_input.0.clear(&keyboard_events);
+}
+
+pub(crate) fn format_date(timestamp: u64) -> String {
+    let days = (timestamp / 86400) as i64 + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days.rem_euclid(146097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+fn cycle_character(character: char, step: isize) -> char {
+    let characters = NAME_CHARACTERS.as_bytes();
+    let index = characters
+        .iter()
+        .position(|candidate| *candidate as char == character)
+        .unwrap_or_default() as isize;
+
+    characters[(index + step).rem_euclid(characters.len() as isize) as usize] as char
+}
+
+pub(crate) fn enter_name(
+    mut name_entry_input: ResMut<NameEntryInput>,
+    keyboard_events: Res<Events<KeyboardInput>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    action_state: Res<ActionState>,
+    mut player_name: ResMut<PlayerName>,
+    mut high_scores: ResMut<HighScores>,
+    game_over_stats: Res<GameOverStats>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    for keyboard_event in name_entry_input.0.read(&keyboard_events) {
+        if keyboard_event.state != ButtonState::Pressed
+            || !keys.just_pressed(keyboard_event.key_code)
+        {
+            continue;
+        }
+
+        match &keyboard_event.logical_key {
+            Key::Character(characters) => {
+                for character in characters.chars() {
+                    if (character.is_ascii_alphanumeric() || character == ' ')
+                        && player_name.0.len() < MAX_NAME_LENGTH
+                    {
+                        player_name.0.push(character.to_ascii_uppercase());
+                    }
+                }
+            }
+            Key::Space if player_name.0.len() < MAX_NAME_LENGTH => player_name.0.push(' '),
+            Key::Backspace => {
+                player_name.0.pop();
+            }
+            _ => (),
+        }
+    }
+
+    if keys.get_just_pressed().next().is_none() {
+        if action_state.just_pressed(Action::MoveRight) && player_name.0.len() < MAX_NAME_LENGTH {
+            player_name.0.push('A');
+        }
+
+        if action_state.just_pressed(Action::MoveLeft) {
+            player_name.0.pop();
+        }
+
+        for (action, step) in [(Action::MoveUp, 1), (Action::MoveDown, -1)] {
+            if action_state.just_pressed(action) {
+                let character = player_name
+                    .0
+                    .pop()
+                    .map_or('A', |character| cycle_character(character, step));
+                player_name.0.push(character);
+            }
+        }
+    }
+
+    let skipped = action_state.just_pressed(Action::Back);
+    if !skipped && !action_state.just_pressed(Action::Confirm) {
+        return;
+    }
+
+    let name = player_name.0.trim();
+    let date = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default();
+
+    high_scores.insert(HighScore {
+        name: if name.is_empty() || skipped {
+            "???"
+        } else {
+            name
+        }
+        .to_string(),
+        score: game_over_stats.score,
+        level: game_over_stats.level,
+        time_played: game_over_stats.time_played,
+        date,
+    });
+    high_scores.store();
+    player_name.0.clear();
+    next_state.set(if game_over_stats.victory {
+        GameState::Victory
+    } else {
+        GameState::GameOver
+    });
+}
+
+pub(crate) fn toggle_high_scores(
+    action_state: Res<ActionState>,
+    game_state: Res<State<GameState>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    match game_state.get() {
+        GameState::Menu if action_state.just_pressed(Action::HighScores) => {
+            next_state.set(GameState::HighScores);
+        }
+        GameState::HighScores
+            if action_state.just_pressed(Action::HighScores)
+                || action_state.just_pressed(Action::Back) =>
+        {
+            next_state.set(GameState::Menu);
+        }
+        _ => (),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn high_score(name: &str, score: f32) -> HighScore {
+        HighScore {
+            name: name.to_string(),
+            score,
+            level: 1,
+            time_played: 60.0,
+            date: 0,
+        }
+    }
+
+    fn names(high_scores: &HighScores) -> Vec<&str> {
+        high_scores
+            .entries
+            .iter()
+            .map(|entry| entry.name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn name_characters_wrap_around() {
+        assert_eq!(cycle_character('A', 1), 'B');
+        assert_eq!(cycle_character('A', -1), ' ');
+        assert_eq!(cycle_character(' ', 1), 'A');
+        assert_eq!(cycle_character('Z', 1), '0');
+    }
+
+    #[test]
+    fn empty_table_accepts_any_score() {
+        let mut high_scores = HighScores::default();
+
+        assert!(high_scores.qualifies(0.0));
+        assert_eq!(high_scores.insert(high_score("A", -100.0)), Some(0));
+    }
+
+    #[test]
+    fn entries_are_ranked_by_descending_score() {
+        let mut high_scores = HighScores::default();
+
+        assert_eq!(high_scores.insert(high_score("A", 100.0)), Some(0));
+        assert_eq!(high_scores.insert(high_score("B", 300.0)), Some(0));
+        assert_eq!(high_scores.insert(high_score("C", 200.0)), Some(1));
+
+        assert_eq!(names(&high_scores), ["B", "C", "A"]);
+    }
+
+    #[test]
+    fn ties_keep_the_earlier_entry_ahead() {
+        let mut high_scores = HighScores::default();
+
+        high_scores.insert(high_score("A", 100.0));
+        assert_eq!(high_scores.insert(high_score("B", 100.0)), Some(1));
+
+        assert_eq!(names(&high_scores), ["A", "B"]);
+    }
+
+    #[test]
+    fn full_table_drops_the_lowest_entry() {
+        let mut high_scores = HighScores::default();
+
+        for score in 1..=MAX_HIGH_SCORES {
+            high_scores.insert(high_score(&score.to_string(), score as f32 * 10.0));
+        }
+
+        assert!(!high_scores.qualifies(10.0));
+        assert_eq!(high_scores.insert(high_score("LOW", 5.0)), None);
+        assert_eq!(high_scores.insert(high_score("NEW", 15.0)), Some(9));
+        assert_eq!(high_scores.entries.len(), MAX_HIGH_SCORES);
+        assert_eq!(high_scores.entries.last().unwrap().name, "NEW");
+    }
+
+    #[test]
+    fn dates_are_formatted_from_unix_timestamps() {
+        assert_eq!(format_date(0), "1970-01-01");
+        assert_eq!(format_date(951_782_400), "2000-02-29");
+        assert_eq!(format_date(1_704_067_199), "2023-12-31");
+    }
+}
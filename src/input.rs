@@ -0,0 +1,390 @@
+use bevy::{prelude::*, utils::HashMap, utils::HashSet};
+use serde::{Deserialize, Serialize};
+
+use crate::consts::*;
+use crate::gameplay::GameState;
+use crate::storage;
+use crate::touch::TouchActions;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+pub(crate) enum Action {
+    MoveUp,
+    MoveDown,
+    MoveLeft,
+    MoveRight,
+    Shove,
+    Pause,
+    ToggleMusic,
+    Restart,
+    Quit,
+    Retry,
+    Back,
+    Continue,
+    HighScores,
+    Controls,
+    LevelSelect,
+    Confirm,
+}
+
+impl Action {
+    pub(crate) const ALL: [Action; 16] = [
+        Action::MoveUp,
+        Action::MoveDown,
+        Action::MoveLeft,
+        Action::MoveRight,
+        Action::Shove,
+        Action::Pause,
+        Action::ToggleMusic,
+        Action::Restart,
+        Action::Quit,
+        Action::Retry,
+        Action::Back,
+        Action::Continue,
+        Action::HighScores,
+        Action::Controls,
+        Action::LevelSelect,
+        Action::Confirm,
+    ];
+
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            Action::MoveUp => "MOVE UP",
+            Action::MoveDown => "MOVE DOWN",
+            Action::MoveLeft => "MOVE LEFT",
+            Action::MoveRight => "MOVE RIGHT",
+            Action::Shove => "SHOVE",
+            Action::Pause => "START/PAUSE",
+            Action::ToggleMusic => "TOGGLE MUSIC",
+            Action::Restart => "RESTART",
+            Action::Quit => "QUIT",
+            Action::Retry => "RETRY LEVEL",
+            Action::Back => "BACK",
+            Action::Continue => "CONTINUE",
+            Action::HighScores => "HIGH SCORES",
+            Action::Controls => "CONTROLS",
+            Action::LevelSelect => "LEVEL SELECT",
+            Action::Confirm => "CONFIRM",
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub(crate) enum AxisDirection {
+    Positive,
+    Negative,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub(crate) enum Binding {
+    Key(KeyCode),
+    GamepadButton(GamepadButtonType),
+    GamepadAxis(GamepadAxisType, AxisDirection),
+}
+
+impl Binding {
+    fn is_keyboard(&self) -> bool {
+        matches!(self, Binding::Key(_))
+    }
+
+    pub(crate) fn label(&self) -> String {
+        match self {
+            Binding::Key(key_code) => {
+                let key_code = format!("{:?}", key_code);
+                key_code
+                    .trim_start_matches("Key")
+                    .trim_start_matches("Digit")
+                    .to_uppercase()
+            }
+            Binding::GamepadButton(button_type) => format!("PAD {:?}", button_type).to_uppercase(),
+            Binding::GamepadAxis(axis_type, direction) => format!(
+                "PAD {:?}{}",
+                axis_type,
+                match direction {
+                    AxisDirection::Positive => "+",
+                    AxisDirection::Negative => "-",
+                }
+            )
+            .to_uppercase(),
+        }
+    }
+}
+
+#[derive(Resource, Serialize, Deserialize)]
+pub(crate) struct InputBindings {
+    bindings: HashMap<Action, Vec<Binding>>,
+}
+
+impl Default for InputBindings {
+    fn default() -> Self {
+        use AxisDirection::*;
+        use Binding::*;
+
+        InputBindings {
+            bindings: HashMap::from([
+                (
+                    Action::MoveUp,
+                    vec![
+                        Key(KeyCode::KeyW),
+                        Key(KeyCode::ArrowUp),
+                        GamepadButton(GamepadButtonType::DPadUp),
+                        GamepadAxis(GamepadAxisType::LeftStickY, Positive),
+                    ],
+                ),
+                (
+                    Action::MoveDown,
+                    vec![
+                        Key(KeyCode::KeyS),
+                        Key(KeyCode::ArrowDown),
+                        GamepadButton(GamepadButtonType::DPadDown),
+                        GamepadAxis(GamepadAxisType::LeftStickY, Negative),
+                    ],
+                ),
+                (
+                    Action::MoveLeft,
+                    vec![
+                        Key(KeyCode::KeyA),
+                        Key(KeyCode::ArrowLeft),
+                        GamepadButton(GamepadButtonType::DPadLeft),
+                        GamepadAxis(GamepadAxisType::LeftStickX, Negative),
+                    ],
+                ),
+                (
+                    Action::MoveRight,
+                    vec![
+                        Key(KeyCode::KeyD),
+                        Key(KeyCode::ArrowRight),
+                        GamepadButton(GamepadButtonType::DPadRight),
+                        GamepadAxis(GamepadAxisType::LeftStickX, Positive),
+                    ],
+                ),
+                (
+                    Action::Shove,
+                    vec![
+                        Key(KeyCode::KeyE),
+                        Key(KeyCode::ShiftRight),
+                        GamepadButton(GamepadButtonType::RightTrigger),
+                    ],
+                ),
+                (
+                    Action::Pause,
+                    vec![
+                        Key(KeyCode::Space),
+                        Key(KeyCode::KeyP),
+                        GamepadButton(GamepadButtonType::Start),
+                    ],
+                ),
+                (
+                    Action::ToggleMusic,
+                    vec![Key(KeyCode::KeyM), GamepadButton(GamepadButtonType::Select)],
+                ),
+                (Action::Restart, vec![Key(KeyCode::F5)]),
+                (Action::Quit, vec![Key(KeyCode::KeyQ)]),
+                (
+                    Action::Retry,
+                    vec![Key(KeyCode::KeyR), GamepadButton(GamepadButtonType::South)],
+                ),
+                (
+                    Action::Back,
+                    vec![Key(KeyCode::Escape), GamepadButton(GamepadButtonType::East)],
+                ),
+                (
+                    Action::Continue,
+                    vec![Key(KeyCode::KeyC), GamepadButton(GamepadButtonType::West)],
+                ),
+                (
+                    Action::HighScores,
+                    vec![Key(KeyCode::KeyH), GamepadButton(GamepadButtonType::North)],
+                ),
+                (Action::Controls, vec![Key(KeyCode::KeyK)]),
+                (Action::LevelSelect, vec![Key(KeyCode::KeyL)]),
+                (
+                    Action::Confirm,
+                    vec![Key(KeyCode::Enter), GamepadButton(GamepadButtonType::South)],
+                ),
+            ]),
+        }
+    }
+}
+
+impl InputBindings {
+    pub(crate) fn load() -> Self {
+        let mut input_bindings = InputBindings::default();
+
+        if let Some(stored) = storage::read(SETTINGS_FILE_NAME)
+            .and_then(|contents| serde_json::from_str::<InputBindings>(&contents).ok())
+        {
+            input_bindings.bindings.extend(stored.bindings);
+        }
+
+        input_bindings
+    }
+
+    fn store(&self) {
+        match serde_json::to_string(self) {
+            Ok(contents) => storage::write(SETTINGS_FILE_NAME, &contents),
+            Err(err) => warn!("failed to serialize input bindings: {}", err),
+        }
+    }
+
+    pub(crate) fn get(&self, action: Action) -> &[Binding] {
+        self.bindings
+            .get(&action)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn describe(&self, action: Action) -> String {
+        self.get(action)
+            .iter()
+            .find(|binding| binding.is_keyboard())
+            .or(self.get(action).first())
+            .map(Binding::label)
+            .unwrap_or_else(|| "-".to_string())
+    }
+
+    fn rebind(&mut self, action: Action, binding: Binding) {
+        let bindings = self.bindings.entry(action).or_default();
+
+        match bindings
+            .iter_mut()
+            .find(|existing| existing.is_keyboard() == binding.is_keyboard())
+        {
+            Some(existing) => *existing = binding,
+            None => bindings.push(binding),
+        }
+    }
+}
+
+#[derive(Default, Resource)]
+pub(crate) struct ActionState {
+    pressed: HashSet<Action>,
+    previous: HashSet<Action>,
+}
+
+impl ActionState {
+    pub(crate) fn pressed(&self, action: Action) -> bool {
+        self.pressed.contains(&action)
+    }
+
+    pub(crate) fn just_pressed(&self, action: Action) -> bool {
+        self.pressed.contains(&action) && !self.previous.contains(&action)
+    }
+
+    pub(crate) fn just_released(&self, action: Action) -> bool {
+        !self.pressed.contains(&action) && self.previous.contains(&action)
+    }
+}
+
+pub(crate) fn action_just_pressed(action: Action) -> impl Fn(Res<ActionState>) -> bool + Clone {
+    move |action_state: Res<ActionState>| action_state.just_pressed(action)
+}
+
+pub(crate) fn update_action_state(
+    mut action_state: ResMut<ActionState>,
+    input_bindings: Res<InputBindings>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    gamepads: Res<Gamepads>,
+    gamepad_buttons: Res<ButtonInput<GamepadButton>>,
+    gamepad_axes: Res<Axis<GamepadAxis>>,
+    touch_actions: Res<TouchActions>,
+) {
+    let action_state = action_state.as_mut();
+    action_state.previous = std::mem::take(&mut action_state.pressed);
+    action_state.pressed.extend(touch_actions.0.iter().copied());
+
+    for action in Action::ALL {
+        let active = input_bindings
+            .get(action)
+            .iter()
+            .any(|binding| match *binding {
+                Binding::Key(key_code) => keyboard.pressed(key_code),
+                Binding::GamepadButton(button_type) => gamepads.iter().any(|gamepad| {
+                    gamepad_buttons.pressed(GamepadButton::new(gamepad, button_type))
+                }),
+                Binding::GamepadAxis(axis_type, direction) => gamepads.iter().any(|gamepad| {
+                    let value = gamepad_axes
+                        .get(GamepadAxis::new(gamepad, axis_type))
+                        .unwrap_or_default();
+
+                    match direction {
+                        AxisDirection::Positive => value > GAMEPAD_AXIS_THRESHOLD,
+                        AxisDirection::Negative => value < -GAMEPAD_AXIS_THRESHOLD,
+                    }
+                }),
+            });
+
+        if active {
+            action_state.pressed.insert(action);
+        }
+    }
+}
+
+#[derive(Default, Resource)]
+pub(crate) struct Rebinding {
+    pub(crate) selected: usize,
+    pub(crate) waiting: bool,
+}
+
+pub(crate) fn toggle_controls(
+    action_state: Res<ActionState>,
+    game_state: Res<State<GameState>>,
+    rebinding: Res<Rebinding>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    match game_state.get() {
+        GameState::Menu if action_state.just_pressed(Action::Controls) => {
+            next_state.set(GameState::Controls);
+        }
+        GameState::Controls if !rebinding.waiting && action_state.just_pressed(Action::Back) => {
+            next_state.set(GameState::Menu);
+        }
+        _ => (),
+    }
+}
+
+pub(crate) fn rebind_controls(
+    action_state: Res<ActionState>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    gamepad_buttons: Res<ButtonInput<GamepadButton>>,
+    mut rebinding: ResMut<Rebinding>,
+    mut input_bindings: ResMut<InputBindings>,
+) {
+    if !rebinding.waiting {
+        if action_state.just_pressed(Action::MoveUp) {
+            rebinding.selected = rebinding
+                .selected
+                .checked_sub(1)
+                .unwrap_or(Action::ALL.len() - 1);
+        }
+
+        if action_state.just_pressed(Action::MoveDown) {
+            rebinding.selected = (rebinding.selected + 1) % Action::ALL.len();
+        }
+
+        if action_state.just_pressed(Action::Confirm) {
+            rebinding.waiting = true;
+        }
+
+        return;
+    }
+
+    let binding = keyboard
+        .get_just_pressed()
+        .next()
+        .map(|key_code| Binding::Key(*key_code))
+        .or_else(|| {
+            gamepad_buttons
+                .get_just_pressed()
+                .next()
+                .map(|button| Binding::GamepadButton(button.button_type))
+        });
+
+    if let Some(binding) = binding {
+        if binding != Binding::Key(KeyCode::Escape) {
+            input_bindings.rebind(Action::ALL[rebinding.selected], binding);
+            input_bindings.store();
+        }
+
+        rebinding.waiting = false;
+    }
+}
@@ -2,103 +2,121 @@ use bevy::prelude::*;
 use bevy_ecs_ldtk::prelude::*;
 
 use crate::consts::*;
+use crate::fields::float_field_or;
 use crate::player::{Player, PlayerStats};
 
-#[derive(Default, Component)]
-pub(crate) struct Key;
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum PickupEffect {
+    Score(f32),
+    Heal(f32),
+    Key,
+}
 
-#[derive(Default, Bundle, LdtkEntity)]
-pub(crate) struct KeyBundle {
-    pub key: Key,
-    #[sprite_sheet_bundle]
-    pub sprite_sheet_bundle: LdtkSpriteSheetBundle,
-    #[grid_coords]
-    grid_coords: GridCoords,
+impl PickupEffect {
+    fn apply(&self, player_stats: &mut PlayerStats) {
+        match *self {
+            PickupEffect::Score(score) => player_stats.score += score,
+            PickupEffect::Heal(health) => {
+                player_stats.health = (player_stats.health + health).min(PLAYER_MAX_HEALTH);
+            }
+            PickupEffect::Key => {
+                player_stats.keys += 1;
+                player_stats.keys_collected += 1;
+            }
+        }
+    }
 }
 
-#[derive(Default, Component)]
-pub(crate) struct Carrot;
+const PICKUP_DEFINITIONS: &[(&str, PickupEffect)] = &[
+    ("Key", PickupEffect::Key),
+    ("Carrot", PickupEffect::Heal(CARROT_HEALTH)),
+    ("Bronze", PickupEffect::Score(BRONZE_SCORE)),
+];
 
-#[derive(Default, Bundle, LdtkEntity)]
-pub(crate) struct CarrotBundle {
-    pub carrot: Carrot,
-    #[sprite_sheet_bundle]
-    pub sprite_sheet_bundle: LdtkSpriteSheetBundle,
-    #[grid_coords]
-    grid_coords: GridCoords,
+#[derive(Component)]
+pub(crate) struct Pickup {
+    pub(crate) effect: PickupEffect,
 }
 
-#[derive(Default, Component)]
-pub(crate) struct Bronze;
+impl Default for Pickup {
+    fn default() -> Self {
+        Pickup {
+            effect: PickupEffect::Score(0.0),
+        }
+    }
+}
+
+fn pickup(entity_instance: &EntityInstance) -> Pickup {
+    let effect = PICKUP_DEFINITIONS
+        .iter()
+        .find(|(identifier, _)| *identifier == entity_instance.identifier)
+        .map(|(_, effect)| *effect)
+        .expect("registered pickup should have a pickup definition");
+
+    let effect = match effect {
+        PickupEffect::Score(score) => {
+            PickupEffect::Score(float_field_or(entity_instance, "score", score))
+        }
+        PickupEffect::Heal(health) => {
+            PickupEffect::Heal(float_field_or(entity_instance, "heal", health))
+        }
+        PickupEffect::Key => PickupEffect::Key,
+    };
+
+    Pickup { effect }
+}
 
 #[derive(Default, Bundle, LdtkEntity)]
-pub(crate) struct BronzeBundle {
-    pub carrot: Bronze,
+pub(crate) struct PickupBundle {
+    #[with(pickup)]
+    pub pickup: Pickup,
     #[sprite_sheet_bundle]
     pub sprite_sheet_bundle: LdtkSpriteSheetBundle,
     #[grid_coords]
     grid_coords: GridCoords,
 }
 
-pub(crate) fn check_keys(
+#[derive(Event)]
+pub(crate) struct ItemCollected {
+    pub(crate) effect: PickupEffect,
+}
+
+pub(crate) fn collect_pickups(
     player_grid_pos: Query<&GridCoords, (With<Player>, Changed<GridCoords>)>,
-    key_entity_grid_pos: Query<(Entity, &GridCoords), With<Key>>,
-    mut player_stats: ResMut<PlayerStats>,
+    pickups: Query<(Entity, &GridCoords, &Pickup)>,
+    mut item_collected: EventWriter<ItemCollected>,
     mut commands: Commands,
-    asset_server: Res<AssetServer>,
 ) {
     if let Ok(player_grid_pos) = player_grid_pos.get_single() {
-        for (key_entity, key_grid_pos) in &key_entity_grid_pos {
-            if player_grid_pos == key_grid_pos {
-                player_stats.keys += 1;
-                commands.entity(key_entity).despawn();
-                commands.spawn(AudioBundle {
-                    source: asset_server.load(ITEM_SOUND_PATH),
-                    ..default()
+        for (pickup_entity, pickup_grid_pos, pickup) in &pickups {
+            if player_grid_pos == pickup_grid_pos {
+                commands.entity(pickup_entity).despawn();
+                item_collected.send(ItemCollected {
+                    effect: pickup.effect,
                 });
             }
         }
     }
 }
 
-pub(crate) fn check_carrots(
-    player_grid_pos: Query<&GridCoords, (With<Player>, Changed<GridCoords>)>,
-    carrot_entity_grid_pos: Query<(Entity, &GridCoords), With<Carrot>>,
+pub(crate) fn apply_pickup_effects(
+    mut item_collected: EventReader<ItemCollected>,
     mut player_stats: ResMut<PlayerStats>,
-    mut commands: Commands,
-    asset_server: Res<AssetServer>,
 ) {
-    if let Ok(player_grid_pos) = player_grid_pos.get_single() {
-        for (carrot_entity, carrot_grid_pos) in &carrot_entity_grid_pos {
-            if player_grid_pos == carrot_grid_pos {
-                player_stats.health = (player_stats.health + CARROT_HEALTH).min(PLAYER_MAX_HEALTH);
-                commands.entity(carrot_entity).despawn();
-                commands.spawn(AudioBundle {
-                    source: asset_server.load(ITEM_SOUND_PATH),
-                    ..default()
-                });
-            }
-        }
+    for item_collected in item_collected.read() {
+        item_collected.effect.apply(&mut player_stats);
     }
 }
 
-pub(crate) fn check_bronze(
-    player_grid_pos: Query<&GridCoords, (With<Player>, Changed<GridCoords>)>,
-    bronze_entity_grid_pos: Query<(Entity, &GridCoords), With<Bronze>>,
-    mut player_stats: ResMut<PlayerStats>,
+pub(crate) fn play_item_sound(
+    mut item_collected: EventReader<ItemCollected>,
     mut commands: Commands,
     asset_server: Res<AssetServer>,
 ) {
-    if let Ok(player_grid_pos) = player_grid_pos.get_single() {
-        for (bronze_entity, bronze_grid_pos) in &bronze_entity_grid_pos {
-            if player_grid_pos == bronze_grid_pos {
-                player_stats.score += BRONZE_SCORE;
-                commands.entity(bronze_entity).despawn();
-                commands.spawn(AudioBundle {
-                    source: asset_server.load(ITEM_SOUND_PATH),
-                    ..default()
-                });
-            }
-        }
+    for _ in item_collected.read() {
+        commands.spawn(AudioBundle {
+            source: asset_server.load(ITEM_SOUND_PATH),
+            ..default()
+        });
     }
 }
@@ -0,0 +1,286 @@
+use bevy::{prelude::*, time::Stopwatch, utils::HashMap};
+use bevy_ecs_ldtk::{ldtk::Level, prelude::*};
+use serde::{Deserialize, Serialize};
+
+use crate::consts::*;
+use crate::fields::string_field;
+use crate::gameplay::{BackgroundMusic, GameState};
+use crate::input::{Action, ActionState};
+use crate::items::{ItemCollected, PickupEffect};
+use crate::player::{finished_timer, PlayerHit, PlayerStats};
+use crate::save::{SaveData, SavedGame};
+use crate::storage;
+use crate::teleport::ScreenFade;
+
+#[derive(Resource)]
+pub(crate) struct LdtkProjectHandle(pub(crate) Handle<LdtkProject>);
+
+impl LdtkProjectHandle {
+    pub(crate) fn levels<'a>(
+        &self,
+        ldtk_project_assets: &'a Assets<LdtkProject>,
+    ) -> Option<&'a [Level]> {
+        ldtk_project_assets
+            .get(&self.0)
+            .map(|ldtk_project| ldtk_project.root_levels())
+    }
+}
+
+pub(crate) fn level_name(level: &Level) -> &str {
+    string_field(level, "display_name").unwrap_or(&level.identifier)
+}
+
+#[derive(Default, Resource, Serialize, Deserialize)]
+pub(crate) struct LevelProgress {
+    unlocked: usize,
+    best_times: HashMap<String, f32>,
+}
+
+impl LevelProgress {
+    pub(crate) fn load() -> Self {
+        storage::read(PROGRESS_FILE_NAME)
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    fn store(&self) {
+        match serde_json::to_string(self) {
+            Ok(contents) => storage::write(PROGRESS_FILE_NAME, &contents),
+            Err(err) => warn!("failed to serialize level progress: {}", err),
+        }
+    }
+
+    pub(crate) fn record(&mut self, index: usize, level: &Level, level_stats: &LevelStats) {
+        let time = level_stats.time.elapsed_secs();
+
+        self.unlocked = self.unlocked.max(index + 1);
+        self.best_times
+            .entry(level.iid.clone())
+            .and_modify(|best_time| *best_time = best_time.min(time))
+            .or_insert(time);
+        self.store();
+    }
+
+    pub(crate) fn unlocked(&self, index: usize, saved_game: &SavedGame) -> bool {
+        index <= self.unlocked
+            || saved_game
+                .0
+                .as_ref()
+                .is_some_and(|save| index <= save.level)
+    }
+
+    pub(crate) fn best_time(&self, level: &Level) -> Option<f32> {
+        self.best_times.get(&level.iid).copied()
+    }
+}
+
+#[derive(Default, Resource)]
+pub(crate) struct LevelSelectCursor(pub(crate) usize);
+
+#[derive(Default, Resource)]
+pub(crate) struct LevelStats {
+    time: Stopwatch,
+    bronze_collected: usize,
+    damage_taken: f32,
+}
+
+#[derive(Default, Resource)]
+pub(crate) struct LevelSummary {
+    pub(crate) level: usize,
+    pub(crate) time: f32,
+    pub(crate) bronze_collected: usize,
+    pub(crate) bronze_total: usize,
+    pub(crate) damage_taken: f32,
+    pub(crate) score_delta: f32,
+}
+
+impl LevelSummary {
+    pub(crate) fn new(
+        level_stats: &LevelStats,
+        player_stats: &PlayerStats,
+        level: usize,
+        bronze_remaining: usize,
+    ) -> Self {
+        LevelSummary {
+            level,
+            time: level_stats.time.elapsed_secs(),
+            bronze_collected: level_stats.bronze_collected,
+            bronze_total: level_stats.bronze_collected + bronze_remaining,
+            damage_taken: level_stats.damage_taken,
+            score_delta: player_stats.score - player_stats.level_start_score,
+        }
+    }
+}
+
+#[derive(Resource)]
+pub(crate) struct LevelTransition {
+    fade_out: Option<Timer>,
+    fade_in: Timer,
+}
+
+impl Default for LevelTransition {
+    fn default() -> Self {
+        LevelTransition {
+            fade_out: None,
+            fade_in: finished_timer(LEVEL_FADE_DURATION),
+        }
+    }
+}
+
+pub(crate) fn reset_level_stats(
+    mut level_events: EventReader<LevelEvent>,
+    mut level_stats: ResMut<LevelStats>,
+) {
+    for level_event in level_events.read() {
+        if let LevelEvent::Spawned(_) = level_event {
+            *level_stats = LevelStats::default();
+        }
+    }
+}
+
+pub(crate) fn track_level_stats(
+    mut level_stats: ResMut<LevelStats>,
+    mut items_collected: EventReader<ItemCollected>,
+    mut player_hits: EventReader<PlayerHit>,
+    time: Res<Time>,
+) {
+    level_stats.time.tick(time.delta());
+
+    for item_collected in items_collected.read() {
+        if let PickupEffect::Score(_) = item_collected.effect {
+            level_stats.bronze_collected += 1;
+        }
+    }
+
+    for player_hit in player_hits.read() {
+        level_stats.damage_taken += player_hit.damage;
+    }
+}
+
+pub(crate) fn advance_level(
+    action_state: Res<ActionState>,
+    touches: Res<Touches>,
+    mut level_transition: ResMut<LevelTransition>,
+    mut screen_fade: Query<&mut BackgroundColor, With<ScreenFade>>,
+    mut player_stats: ResMut<PlayerStats>,
+    level_selection: ResMut<LevelSelection>,
+    mut saved_game: ResMut<SavedGame>,
+    mut next_state: ResMut<NextState<GameState>>,
+    time: Res<Time>,
+) {
+    if level_transition.fade_out.is_none()
+        && (action_state.just_pressed(Action::Confirm) || touches.any_just_pressed())
+    {
+        level_transition.fade_out = Some(Timer::from_seconds(LEVEL_FADE_DURATION, TimerMode::Once));
+    }
+
+    let Some(fade_out) = &mut level_transition.fade_out else {
+        return;
+    };
+    fade_out.tick(time.delta());
+
+    if let Ok(mut background_color) = screen_fade.get_single_mut() {
+        background_color.0 = Color::BLACK.with_alpha(fade_out.fraction());
+    }
+
+    if fade_out.finished() {
+        let indices = match level_selection.into_inner() {
+            LevelSelection::Indices(indices) => indices,
+            _ => panic!("level selection should always be Indices in this game"),
+        };
+
+        player_stats.start_next_level();
+        indices.level += 1;
+        saved_game.store(SaveData::new(&player_stats, indices.level));
+        next_state.set(GameState::Running);
+    }
+}
+
+pub(crate) fn start_level_fade_in(mut level_transition: ResMut<LevelTransition>) {
+    level_transition.fade_out = None;
+    level_transition.fade_in.reset();
+}
+
+pub(crate) fn fade_in_level(
+    mut level_transition: ResMut<LevelTransition>,
+    mut screen_fade: Query<&mut BackgroundColor, With<ScreenFade>>,
+    time: Res<Time>,
+) {
+    if level_transition.fade_in.finished() {
+        return;
+    }
+
+    level_transition.fade_in.tick(time.delta());
+
+    if let Ok(mut background_color) = screen_fade.get_single_mut() {
+        background_color.0 = Color::BLACK.with_alpha(level_transition.fade_in.fraction_remaining());
+    }
+}
+
+pub(crate) fn toggle_level_select(
+    action_state: Res<ActionState>,
+    ldtk_project_handle: Res<LdtkProjectHandle>,
+    ldtk_project_assets: Res<Assets<LdtkProject>>,
+    game_state: Res<State<GameState>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    match game_state.get() {
+        GameState::Menu
+            if action_state.just_pressed(Action::LevelSelect)
+                && ldtk_project_handle.levels(&ldtk_project_assets).is_some() =>
+        {
+            next_state.set(GameState::LevelSelect);
+        }
+        GameState::LevelSelect
+            if action_state.just_pressed(Action::LevelSelect)
+                || action_state.just_pressed(Action::Back) =>
+        {
+            next_state.set(GameState::Menu);
+        }
+        _ => (),
+    }
+}
+
+pub(crate) fn select_level(
+    action_state: Res<ActionState>,
+    ldtk_project_handle: Res<LdtkProjectHandle>,
+    ldtk_project_assets: Res<Assets<LdtkProject>>,
+    level_progress: Res<LevelProgress>,
+    saved_game: Res<SavedGame>,
+    mut level_select_cursor: ResMut<LevelSelectCursor>,
+    mut player_stats: ResMut<PlayerStats>,
+    mut level_selection: ResMut<LevelSelection>,
+    mut next_state: ResMut<NextState<GameState>>,
+    audio_sink: Query<&AudioSink, With<BackgroundMusic>>,
+) {
+    let Some(levels) = ldtk_project_handle.levels(&ldtk_project_assets) else {
+        return;
+    };
+
+    if levels.is_empty() {
+        return;
+    }
+
+    if action_state.just_pressed(Action::MoveUp) {
+        level_select_cursor.0 = level_select_cursor
+            .0
+            .checked_sub(1)
+            .unwrap_or(levels.len() - 1);
+    }
+
+    if action_state.just_pressed(Action::MoveDown) {
+        level_select_cursor.0 = (level_select_cursor.0 + 1) % levels.len();
+    }
+
+    if action_state.just_pressed(Action::Confirm)
+        && level_progress.unlocked(level_select_cursor.0, &saved_game)
+    {
+        *player_stats = PlayerStats::default();
+        *level_selection = LevelSelection::index(level_select_cursor.0);
+        next_state.set(GameState::Running);
+
+        if let Ok(audio_sink) = audio_sink.get_single() {
+            audio_sink.play();
+        }
+    }
+}
@@ -3,6 +3,7 @@
 
 use bevy::{
     audio::{PlaybackMode, Volume},
+    input::InputSystem,
     prelude::*,
     window::WindowResolution,
 };
@@ -10,88 +11,325 @@ use bevy_ecs_ldtk::prelude::*;
 
 use collisions::{CollisionBundle, LevelCollisions};
 use consts::*;
-use enemies::CowBundle;
-use gameplay::{BackgroundMusic, GameState, GoalBundle, PortalEntryBundle, PortalExitBundle};
-use items::{BronzeBundle, CarrotBundle, KeyBundle};
-use player::{MainCamera, PlayerBundle, PlayerStats};
+use enemies::{ChickenHouseBundle, CowBundle};
+use gameplay::{
+    BackgroundMusic, GameOverStats, GameState, GoalBundle, PortalEntryBundle, PortalExitBundle,
+};
+use hazards::{ShooterBundle, ThornsBundle};
+use highscores::{HighScores, NameEntryInput, PlayerName};
+use input::{action_just_pressed, Action, ActionState, InputBindings, Rebinding};
+use items::{ItemCollected, PickupBundle};
+use levels::{
+    LdtkProjectHandle, LevelProgress, LevelSelectCursor, LevelStats, LevelSummary, LevelTransition,
+};
+use pathfinding::Pathfinder;
+use player::{CameraShake, MainCamera, PlayerBundle, PlayerHit, PlayerStats};
+use save::SavedGame;
+use teleport::Teleport;
+use touch::TouchActions;
 
+mod animation;
 mod collisions;
+#[cfg(feature = "dev_console")]
+mod console;
 mod consts;
 mod enemies;
+mod fields;
 mod gameplay;
+mod hazards;
+mod highscores;
+mod input;
 mod items;
+mod levels;
+mod pathfinding;
 mod player;
+mod save;
+mod simulation;
+mod storage;
+mod teleport;
+mod touch;
 mod ui;
 
 fn main() {
-    App::new()
-        .add_plugins(
-            DefaultPlugins
-                .set(WindowPlugin {
-                    primary_window: Some(Window {
-                        canvas: Some(CANVAS_NAME.into()),
-                        fit_canvas_to_parent: true,
-                        prevent_default_event_handling: false,
-                        resolution: WindowResolution::new(PHYSICAL_WIDTH, PHYSICAL_HEIGHT),
-                        ..default()
-                    }),
+    let mut app = App::new();
+    app.add_plugins(
+        DefaultPlugins
+            .set(WindowPlugin {
+                primary_window: Some(Window {
+                    canvas: Some(CANVAS_NAME.into()),
+                    fit_canvas_to_parent: true,
+                    prevent_default_event_handling: false,
+                    resolution: WindowResolution::new(PHYSICAL_WIDTH, PHYSICAL_HEIGHT),
                     ..default()
-                })
-                .set(ImagePlugin::default_nearest()),
-        )
-        .add_plugins(LdtkPlugin)
-        .insert_state(GameState::Menu)
-        .insert_resource(ClearColor(BACKGROUND_COLOR))
-        .insert_resource(PlayerStats::default())
-        .insert_resource(LevelCollisions::default())
-        .insert_resource(LevelSelection::index(0))
-        .register_ldtk_entity::<PlayerBundle>("Player")
-        .register_ldtk_entity::<GoalBundle>("Goal")
-        .register_ldtk_entity::<KeyBundle>("Key")
-        .register_ldtk_entity::<CarrotBundle>("Carrot")
-        .register_ldtk_entity::<BronzeBundle>("Bronze")
-        .register_ldtk_entity::<CowBundle>("Cow")
-        .register_ldtk_entity::<PortalEntryBundle>("Portal_Entry")
-        .register_ldtk_entity::<PortalExitBundle>("Portal_Exit")
-        .register_ldtk_int_cell::<CollisionBundle>(1)
-        .add_systems(Startup, setup)
-        .add_systems(Update, (gameplay::toggle_state, gameplay::toggle_music))
-        .add_systems(
-            OnEnter(GameState::Menu),
-            ui::setup_menu.run_if(in_state(GameState::Menu)),
-        )
-        .add_systems(
-            OnExit(GameState::Menu),
-            (ui::despawn_menu, setup_ldtk_world, ui::setup_status_bar).chain(),
+                }),
+                ..default()
+            })
+            .set(ImagePlugin::default_nearest()),
+    )
+    .add_plugins(LdtkPlugin)
+    .insert_state(GameState::Menu)
+    .insert_resource(ClearColor(BACKGROUND_COLOR))
+    .insert_resource(Time::<Fixed>::from_hz(SIMULATION_HZ))
+    .insert_resource(PlayerStats::default())
+    .insert_resource(GameOverStats::default())
+    .insert_resource(SavedGame::load())
+    .insert_resource(HighScores::load())
+    .insert_resource(PlayerName::default())
+    .init_resource::<NameEntryInput>()
+    .insert_resource(InputBindings::load())
+    .insert_resource(ActionState::default())
+    .insert_resource(Rebinding::default())
+    .insert_resource(TouchActions::default())
+    .insert_resource(LevelCollisions::default())
+    .insert_resource(LevelSelection::index(0))
+    .add_event::<ItemCollected>()
+    .add_event::<PlayerHit>()
+    .init_resource::<CameraShake>()
+    .init_resource::<Teleport>()
+    .init_resource::<Pathfinder>()
+    .init_resource::<LevelStats>()
+    .init_resource::<LevelSummary>()
+    .init_resource::<LevelTransition>()
+    .insert_resource(LevelProgress::load())
+    .init_resource::<LevelSelectCursor>()
+    .register_ldtk_entity::<PlayerBundle>("Player")
+    .register_ldtk_entity::<GoalBundle>("Goal")
+    .register_ldtk_entity::<PickupBundle>("Key")
+    .register_ldtk_entity::<PickupBundle>("Carrot")
+    .register_ldtk_entity::<PickupBundle>("Bronze")
+    .register_ldtk_entity::<CowBundle>("Cow")
+    .register_ldtk_entity::<ChickenHouseBundle>("Chicken_House")
+    .register_ldtk_entity::<ThornsBundle>("Thorns")
+    .register_ldtk_entity::<ShooterBundle>("Shooter")
+    .register_ldtk_entity::<PortalEntryBundle>("Portal_Entry")
+    .register_ldtk_entity::<PortalExitBundle>("Portal_Exit")
+    .register_ldtk_int_cell::<CollisionBundle>(1)
+    .add_systems(
+        Startup,
+        (
+            setup,
+            touch::setup_touch_controls,
+            teleport::setup_screen_fade,
+        ),
+    )
+    .add_systems(
+        PreUpdate,
+        (
+            touch::show_touch_controls,
+            touch::read_touch_buttons,
+            input::update_action_state,
         )
-        .add_systems(OnEnter(GameState::PauseMenu), ui::setup_menu)
-        .add_systems(OnExit(GameState::PauseMenu), ui::despawn_menu)
-        .add_systems(OnEnter(GameState::GameOver), ui::setup_game_over)
-        .add_systems(
-            Update,
+            .chain()
+            .after(InputSystem),
+    )
+    .add_systems(
+        PreUpdate,
+        simulation::init_simulated_positions.after(bevy_ecs_ldtk::systems::process_ldtk_levels),
+    )
+    .add_systems(
+        FixedUpdate,
+        (
+            simulation::store_previous_positions,
             (
-                collisions::cache_collision_locations,
-                (player::move_player, player::center_camera).chain(),
+                player::move_player,
                 player::update_player_stats,
-                items::check_keys,
-                items::check_carrots,
-                items::check_bronze,
-                enemies::patrol,
-                gameplay::check_goal,
-                gameplay::check_game_over,
-                gameplay::check_cheats,
-                ui::update_status_bar,
+                enemies::update_behaviour,
+                (
+                    enemies::recover_from_stuns,
+                    enemies::patrol,
+                    enemies::chase,
+                    enemies::spawn_chickens,
+                    enemies::wander,
+                    hazards::fire_projectiles,
+                    hazards::move_projectiles,
+                ),
+                player::check_hostile_contacts,
+                hazards::despawn_projectile_hits,
+                player::apply_player_hits,
             )
+                .chain()
                 .run_if(in_state(GameState::Running)),
         )
-        .add_systems(
-            Update,
-            gameplay::check_portal_entry
-                .run_if(not(in_state(GameState::Menu)))
-                .run_if(not(in_state(GameState::PauseMenu)))
-                .run_if(not(in_state(GameState::GameOver))),
+            .chain(),
+    )
+    .add_systems(
+        Update,
+        (
+            gameplay::toggle_state,
+            gameplay::toggle_music,
+            gameplay::quit_game,
+        )
+            .run_if(not(in_state(GameState::NameEntry)))
+            .run_if(not(in_state(GameState::Controls))),
+    )
+    .add_systems(
+        OnEnter(GameState::Menu),
+        ui::setup_menu.run_if(in_state(GameState::Menu)),
+    )
+    .add_systems(OnExit(GameState::Menu), ui::despawn_menu)
+    .add_systems(
+        OnTransition {
+            exited: GameState::Menu,
+            entered: GameState::Running,
+        },
+        (setup_ldtk_world, ui::setup_status_bar),
+    )
+    .add_systems(
+        OnTransition {
+            exited: GameState::LevelSelect,
+            entered: GameState::Running,
+        },
+        (setup_ldtk_world, ui::setup_status_bar),
+    )
+    .add_systems(
+        Update,
+        (
+            levels::toggle_level_select,
+            (levels::select_level, ui::update_level_select)
+                .chain()
+                .run_if(in_state(GameState::LevelSelect)),
+        )
+            .chain()
+            .run_if(in_state(GameState::Menu).or_else(in_state(GameState::LevelSelect))),
+    )
+    .add_systems(OnEnter(GameState::LevelSelect), ui::setup_level_select)
+    .add_systems(OnExit(GameState::LevelSelect), ui::despawn_menu)
+    .add_systems(
+        Update,
+        save::continue_game.run_if(in_state(GameState::Menu)),
+    )
+    .add_systems(
+        Update,
+        highscores::toggle_high_scores
+            .run_if(in_state(GameState::Menu).or_else(in_state(GameState::HighScores))),
+    )
+    .add_systems(
+        Update,
+        (
+            input::toggle_controls,
+            (input::rebind_controls, ui::update_controls)
+                .chain()
+                .run_if(in_state(GameState::Controls)),
+        )
+            .chain()
+            .run_if(in_state(GameState::Menu).or_else(in_state(GameState::Controls))),
+    )
+    .add_systems(OnEnter(GameState::Controls), ui::setup_controls)
+    .add_systems(OnExit(GameState::Controls), ui::despawn_menu)
+    .add_systems(OnEnter(GameState::HighScores), ui::setup_high_scores)
+    .add_systems(OnExit(GameState::HighScores), ui::despawn_menu)
+    .add_systems(
+        OnEnter(GameState::NameEntry),
+        (highscores::clear_name_entry_input, ui::setup_name_entry),
+    )
+    .add_systems(OnExit(GameState::NameEntry), ui::despawn_menu)
+    .add_systems(
+        Update,
+        (highscores::enter_name, ui::update_name_entry)
+            .chain()
+            .run_if(in_state(GameState::NameEntry)),
+    )
+    .add_systems(OnEnter(GameState::PauseMenu), ui::setup_menu)
+    .add_systems(OnExit(GameState::PauseMenu), ui::despawn_menu)
+    .add_systems(OnEnter(GameState::LevelComplete), ui::setup_level_complete)
+    .add_systems(
+        OnExit(GameState::LevelComplete),
+        (ui::despawn_menu, levels::start_level_fade_in),
+    )
+    .add_systems(
+        Update,
+        levels::advance_level.run_if(in_state(GameState::LevelComplete)),
+    )
+    .add_systems(OnEnter(GameState::Victory), ui::setup_victory)
+    .add_systems(OnExit(GameState::Victory), ui::despawn_menu)
+    .add_systems(OnEnter(GameState::GameOver), ui::setup_game_over)
+    .add_systems(OnExit(GameState::GameOver), ui::despawn_menu)
+    .add_systems(
+        Update,
+        (
+            despawn_ldtk_world,
+            ui::despawn_status_bar,
+            gameplay::restart_game,
+            setup_ldtk_world,
+            ui::setup_status_bar,
         )
-        .run();
+            .chain()
+            .run_if(action_just_pressed(Action::Restart))
+            .run_if(
+                in_state(GameState::Running)
+                    .or_else(in_state(GameState::PauseMenu))
+                    .or_else(in_state(GameState::Teleporting))
+                    .or_else(in_state(GameState::LevelComplete))
+                    .or_else(in_state(GameState::Victory))
+                    .or_else(in_state(GameState::GameOver)),
+            ),
+    )
+    .add_systems(
+        Update,
+        (
+            despawn_ldtk_world,
+            ui::despawn_status_bar,
+            gameplay::retry_level,
+            setup_ldtk_world,
+            ui::setup_status_bar,
+        )
+            .chain()
+            .run_if(action_just_pressed(Action::Retry))
+            .run_if(in_state(GameState::GameOver)),
+    )
+    .add_systems(
+        Update,
+        (
+            despawn_ldtk_world,
+            ui::despawn_status_bar,
+            gameplay::return_to_menu,
+        )
+            .chain()
+            .run_if(action_just_pressed(Action::Back))
+            .run_if(in_state(GameState::GameOver).or_else(in_state(GameState::Victory))),
+    )
+    .add_systems(
+        Update,
+        (
+            collisions::cache_collision_locations,
+            levels::reset_level_stats,
+            levels::track_level_stats,
+            levels::fade_in_level,
+            player::shove,
+            player::flash_hurt_player,
+            player::play_hit_sound,
+            (
+                items::collect_pickups,
+                (items::apply_pickup_effects, items::play_item_sound),
+            )
+                .chain(),
+            (enemies::animate_enemies, animation::animate_sprites).chain(),
+            gameplay::check_goal,
+            gameplay::check_portal_entry,
+            gameplay::check_game_over,
+            ui::update_status_bar,
+        )
+            .run_if(in_state(GameState::Running)),
+    )
+    .add_systems(
+        Update,
+        (
+            simulation::interpolate_transforms,
+            player::shake_camera,
+            player::center_camera,
+        )
+            .chain(),
+    )
+    .add_systems(
+        Update,
+        teleport::run_teleport.run_if(in_state(GameState::Teleporting)),
+    )
+    .add_systems(OnExit(GameState::Teleporting), teleport::clear_teleport);
+
+    #[cfg(feature = "dev_console")]
+    app.add_plugins(console::DevConsolePlugin);
+
+    app.run();
 }
 
 fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
@@ -100,6 +338,7 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
     camera.transform.translation.x = PHYSICAL_WIDTH / 8.0;
     camera.transform.translation.y = PHYSICAL_HEIGHT / 8.0;
     commands.spawn((camera, MainCamera));
+    commands.insert_resource(LdtkProjectHandle(asset_server.load(LDTK_PROJECT_PATH)));
 
     commands.spawn((
         AudioBundle {
@@ -115,9 +354,18 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
     ));
 }
 
-fn setup_ldtk_world(mut commands: Commands, asset_server: Res<AssetServer>) {
+fn setup_ldtk_world(mut commands: Commands, ldtk_project_handle: Res<LdtkProjectHandle>) {
     commands.spawn(LdtkWorldBundle {
-        ldtk_handle: asset_server.load(LDTK_PROJECT_PATH),
+        ldtk_handle: ldtk_project_handle.0.clone(),
         ..default()
     });
 }
+
+fn despawn_ldtk_world(
+    mut commands: Commands,
+    ldtk_worlds: Query<Entity, With<Handle<LdtkProject>>>,
+) {
+    for ldtk_world in &ldtk_worlds {
+        commands.entity(ldtk_world).despawn_recursive();
+    }
+}
@@ -0,0 +1,306 @@
+use bevy::prelude::*;
+use bevy_ecs_ldtk::prelude::*;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+
+use crate::collisions::LevelCollisions;
+use crate::consts::*;
+
+const STRAIGHT_COST: u32 = 10;
+const DIAGONAL_COST: u32 = 14;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub(crate) enum Connectivity {
+    Four,
+    Eight,
+}
+
+impl Connectivity {
+    fn neighbours(&self) -> &'static [(i32, i32)] {
+        match self {
+            Connectivity::Four => &[(1, 0), (-1, 0), (0, 1), (0, -1)],
+            Connectivity::Eight => &[
+                (1, 0),
+                (-1, 0),
+                (0, 1),
+                (0, -1),
+                (1, 1),
+                (1, -1),
+                (-1, 1),
+                (-1, -1),
+            ],
+        }
+    }
+
+    fn heuristic(&self, from: GridCoords, to: GridCoords) -> u32 {
+        let dx = from.x.abs_diff(to.x);
+        let dy = from.y.abs_diff(to.y);
+
+        match self {
+            Connectivity::Four => (dx + dy) * STRAIGHT_COST,
+            Connectivity::Eight => {
+                dx.max(dy) * STRAIGHT_COST + dx.min(dy) * (DIAGONAL_COST - STRAIGHT_COST)
+            }
+        }
+    }
+}
+
+pub(crate) fn find_path(
+    level_collisions: &LevelCollisions,
+    start: GridCoords,
+    goal: GridCoords,
+    connectivity: Connectivity,
+) -> Option<Vec<GridCoords>> {
+    if level_collisions.collision(&goal) {
+        return None;
+    }
+
+    let mut came_from = HashMap::new();
+    let mut costs = HashMap::from([(start, 0)]);
+    let mut open = BinaryHeap::from([(
+        Reverse(connectivity.heuristic(start, goal)),
+        start.x,
+        start.y,
+    )]);
+
+    while let Some((Reverse(estimate), x, y)) = open.pop() {
+        let current = GridCoords::new(x, y);
+
+        if estimate > costs[&current] + connectivity.heuristic(current, goal) {
+            continue;
+        }
+
+        if current == goal {
+            let mut path = vec![current];
+            let mut cell = current;
+
+            while let Some(previous) = came_from.get(&cell) {
+                cell = *previous;
+                path.push(cell);
+            }
+
+            path.reverse();
+            return Some(path);
+        }
+
+        for &(dx, dy) in connectivity.neighbours() {
+            let next = GridCoords::new(current.x + dx, current.y + dy);
+            let diagonal = dx != 0 && dy != 0;
+
+            if level_collisions.collision(&next)
+                || (diagonal
+                    && (level_collisions.collision(&GridCoords::new(current.x + dx, current.y))
+                        || level_collisions.collision(&GridCoords::new(current.x, current.y + dy))))
+            {
+                continue;
+            }
+
+            let cost = costs[&current]
+                + if diagonal {
+                    DIAGONAL_COST
+                } else {
+                    STRAIGHT_COST
+                };
+
+            if costs.get(&next).is_none_or(|&known| cost < known) {
+                costs.insert(next, cost);
+                came_from.insert(next, current);
+                open.push((
+                    Reverse(cost + connectivity.heuristic(next, goal)),
+                    next.x,
+                    next.y,
+                ));
+            }
+        }
+    }
+
+    None
+}
+
+#[derive(Default, Resource)]
+pub(crate) struct Pathfinder {
+    cache: HashMap<(GridCoords, GridCoords, Connectivity), Option<Vec<GridCoords>>>,
+}
+
+impl Pathfinder {
+    pub(crate) fn path(
+        &mut self,
+        level_collisions: &LevelCollisions,
+        start: GridCoords,
+        goal: GridCoords,
+        connectivity: Connectivity,
+    ) -> Option<Vec<GridCoords>> {
+        if self.cache.len() >= MAX_CACHED_PATHS {
+            self.cache.clear();
+        }
+
+        self.cache
+            .entry((start, goal, connectivity))
+            .or_insert_with(|| find_path(level_collisions, start, goal, connectivity))
+            .clone()
+    }
+
+    pub(crate) fn invalidate(&mut self) {
+        self.cache.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path_length(
+        level_collisions: &LevelCollisions,
+        start: (i32, i32),
+        goal: (i32, i32),
+        connectivity: Connectivity,
+    ) -> Option<usize> {
+        find_path(
+            level_collisions,
+            GridCoords::new(start.0, start.1),
+            GridCoords::new(goal.0, goal.1),
+            connectivity,
+        )
+        .map(|path| path.len())
+    }
+
+    #[test]
+    fn open_ground_takes_the_shortest_route() {
+        let level = LevelCollisions::from_rows(&["....", "....", "...."]);
+
+        assert_eq!(
+            path_length(&level, (0, 0), (3, 2), Connectivity::Four),
+            Some(6)
+        );
+        assert_eq!(
+            path_length(&level, (0, 0), (3, 2), Connectivity::Eight),
+            Some(4)
+        );
+        assert_eq!(
+            path_length(&level, (1, 1), (1, 1), Connectivity::Four),
+            Some(1)
+        );
+    }
+
+    fn path_cost(path: &[GridCoords]) -> u32 {
+        path.windows(2)
+            .map(|step| {
+                if step[0].x != step[1].x && step[0].y != step[1].y {
+                    DIAGONAL_COST
+                } else {
+                    STRAIGHT_COST
+                }
+            })
+            .sum()
+    }
+
+    #[test]
+    fn heuristic_is_admissible_and_exact_on_open_ground() {
+        let open = LevelCollisions::from_rows(&["......", "......", "......", "......"]);
+        let walled = LevelCollisions::from_rows(&["......", ".####.", "...#..", "......"]);
+
+        for connectivity in [Connectivity::Four, Connectivity::Eight] {
+            for (start, goal) in (0..6)
+                .flat_map(|x| (0..4).map(move |y| GridCoords::new(x, y)))
+                .flat_map(|start| {
+                    (0..6).flat_map(move |x| (0..4).map(move |y| (start, GridCoords::new(x, y))))
+                })
+            {
+                let estimate = connectivity.heuristic(start, goal);
+                let open_path = find_path(&open, start, goal, connectivity).unwrap();
+                assert_eq!(estimate, path_cost(&open_path));
+
+                if let Some(walled_path) = find_path(&walled, start, goal, connectivity) {
+                    assert!(estimate <= path_cost(&walled_path));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn walls_are_walked_around() {
+        let level = LevelCollisions::from_rows(&["....", ".##.", "...."]);
+        let path = find_path(
+            &level,
+            GridCoords::new(0, 1),
+            GridCoords::new(3, 1),
+            Connectivity::Four,
+        )
+        .unwrap();
+
+        assert_eq!(path.len(), 6);
+        assert!(path.iter().all(|cell| !level.collision(cell)));
+    }
+
+    #[test]
+    fn diagonals_do_not_cut_corners() {
+        let level = LevelCollisions::from_rows(&["..", "#."]);
+
+        assert_eq!(
+            path_length(&level, (1, 0), (0, 1), Connectivity::Eight),
+            Some(3)
+        );
+    }
+
+    #[test]
+    fn unreachable_targets_have_no_path() {
+        let level = LevelCollisions::from_rows(&["..#.", "..#.", "..#."]);
+
+        assert_eq!(
+            path_length(&level, (0, 0), (3, 2), Connectivity::Four),
+            None
+        );
+        assert_eq!(
+            path_length(&level, (0, 0), (3, 2), Connectivity::Eight),
+            None
+        );
+        assert_eq!(
+            path_length(&level, (0, 0), (2, 1), Connectivity::Four),
+            None
+        );
+    }
+
+    #[test]
+    fn level_edges_bound_the_search() {
+        let level = LevelCollisions::from_rows(&["...", "..."]);
+
+        assert_eq!(
+            path_length(&level, (0, 0), (2, 0), Connectivity::Four),
+            Some(3)
+        );
+        assert_eq!(
+            path_length(&level, (0, 0), (-1, 0), Connectivity::Four),
+            None
+        );
+        assert_eq!(
+            path_length(&level, (0, 0), (3, 1), Connectivity::Eight),
+            None
+        );
+        assert_eq!(
+            path_length(&level, (0, 0), (0, 2), Connectivity::Eight),
+            None
+        );
+    }
+
+    #[test]
+    fn cache_is_cleared_on_invalidation() {
+        let open = LevelCollisions::from_rows(&["...", "...", "..."]);
+        let walled = LevelCollisions::from_rows(&["...", "###", "..."]);
+        let mut pathfinder = Pathfinder::default();
+        let start = GridCoords::new(0, 0);
+        let goal = GridCoords::new(0, 2);
+
+        assert!(pathfinder
+            .path(&open, start, goal, Connectivity::Four)
+            .is_some());
+        assert!(pathfinder
+            .path(&walled, start, goal, Connectivity::Four)
+            .is_some());
+
+        pathfinder.invalidate();
+
+        assert!(pathfinder
+            .path(&walled, start, goal, Connectivity::Four)
+            .is_none());
+    }
+}
@@ -1,18 +1,64 @@
-use bevy::prelude::*;
+use bevy::{prelude::*, time::Stopwatch};
 use bevy_ecs_ldtk::prelude::*;
 
-use crate::collisions::LevelCollisions;
+use crate::animation::{AnimationState, SpriteAnimation};
+use crate::collisions::{Hitbox, LevelCollisions};
+#[cfg(feature = "dev_console")]
+use crate::console::DevCheats;
 use crate::consts::*;
-use crate::enemies::Cow;
+use crate::enemies::{Chicken, Hostile, Patrol, Stunned};
+use crate::gameplay::PortalCooldown;
+use crate::hazards::Shooter;
+use crate::input::{Action, ActionState};
+use crate::simulation::SimulatedPosition;
 
 #[derive(Default, Component)]
 pub(crate) struct Player;
 
+fn player_animation(_: &EntityInstance) -> SpriteAnimation {
+    let mut animation = SpriteAnimation::new(
+        PLAYER_ANIMATION_FPS,
+        vec![
+            (AnimationState::IdleDown, PLAYER_IDLE_DOWN_FRAMES),
+            (AnimationState::IdleUp, PLAYER_IDLE_UP_FRAMES),
+            (AnimationState::IdleLeft, PLAYER_IDLE_LEFT_FRAMES),
+            (AnimationState::IdleRight, PLAYER_IDLE_RIGHT_FRAMES),
+            (AnimationState::WalkDown, PLAYER_WALK_DOWN_FRAMES),
+            (AnimationState::WalkUp, PLAYER_WALK_UP_FRAMES),
+            (AnimationState::WalkLeft, PLAYER_WALK_LEFT_FRAMES),
+            (AnimationState::WalkRight, PLAYER_WALK_RIGHT_FRAMES),
+        ],
+    );
+    animation.state = AnimationState::IdleDown;
+    animation
+}
+
+fn player_hitbox(_: &EntityInstance) -> Hitbox {
+    Hitbox::new(PLAYER_HITBOX_HALF_SIZE)
+}
+
+#[derive(Default, Component)]
+pub(crate) struct Knockback(Vec2);
+
+#[derive(Event)]
+pub(crate) struct PlayerHit {
+    pub(crate) damage: f32,
+    pub(crate) score: f32,
+    pub(crate) knockback: Vec2,
+}
+
 #[derive(Default, Bundle, LdtkEntity)]
 pub(crate) struct PlayerBundle {
     player: Player,
-    #[sprite_sheet_bundle("Characters/Basic Charakter Spritesheet.png", 16, 16, 64, 64, 0, 0, 68)]
+    #[sprite_sheet_bundle("Characters/Basic Charakter Spritesheet.png", 48, 48, 4, 4, 0, 0, 0)]
     sprite_sheet_bundle: LdtkSpriteSheetBundle,
+    #[with(player_animation)]
+    animation: SpriteAnimation,
+    #[with(player_hitbox)]
+    hitbox: Hitbox,
+    knockback: Knockback,
+    portal_cooldown: PortalCooldown,
+    simulated_position: SimulatedPosition,
     #[grid_coords]
     grid_coords: GridCoords,
 }
@@ -23,8 +69,37 @@ pub(crate) struct PlayerStats {
     pub(crate) health: f32,
     pub(crate) keys: usize,
     pub(crate) score: f32,
+    pub(crate) keys_collected: usize,
+    pub(crate) level_start_score: f32,
+    pub(crate) time_played: Stopwatch,
     hit_timer: Timer,
-    pub(crate) teleport_timer: Timer,
+    shove_timer: Timer,
+}
+
+impl PlayerStats {
+    pub(crate) fn start_next_level(&mut self) {
+        self.keys = 0;
+        self.health = PLAYER_MAX_HEALTH;
+        self.level_start_score = self.score;
+    }
+
+    pub(crate) fn hurt(&self) -> Option<f32> {
+        (!self.hit_timer.finished()).then(|| self.hit_timer.elapsed_secs())
+    }
+
+    pub(crate) fn shove_cooldown(&self) -> Option<f32> {
+        (!self.shove_timer.finished()).then(|| self.shove_timer.remaining_secs())
+    }
+
+    pub(crate) fn retry_level(&self) -> Self {
+        PlayerStats {
+            score: self.level_start_score,
+            keys_collected: self.keys_collected - self.keys,
+            level_start_score: self.level_start_score,
+            time_played: self.time_played.clone(),
+            ..default()
+        }
+    }
 }
 
 impl Default for PlayerStats {
@@ -33,63 +108,61 @@ impl Default for PlayerStats {
             health: 100.0,
             keys: 0,
             score: 0.0,
-            hit_timer: Timer::from_seconds(0.250, TimerMode::Once),
-            teleport_timer: Timer::from_seconds(1.0, TimerMode::Once),
+            keys_collected: 0,
+            level_start_score: 0.0,
+            time_played: Stopwatch::new(),
+            hit_timer: finished_timer(PLAYER_HIT_COOLDOWN),
+            shove_timer: finished_timer(SHOVE_COOLDOWN),
         }
     }
 }
 
+pub(crate) fn finished_timer(duration: f32) -> Timer {
+    let mut timer = Timer::from_seconds(duration, TimerMode::Once);
+    timer.tick(timer.duration());
+    timer
+}
+
 #[derive(Default, Component)]
 pub(crate) struct MainCamera;
 
 pub(crate) fn move_player(
     mut player_grid_pos: Query<&mut GridCoords, With<Player>>,
-    mut player_transform: Query<&mut Transform, With<Player>>,
-    mut player_atlas: Query<&mut TextureAtlas, With<Player>>,
-    input: Res<ButtonInput<KeyCode>>,
+    mut player_position: Query<(&mut SimulatedPosition, &Hitbox, &mut Knockback), With<Player>>,
+    mut player_animation: Query<&mut SpriteAnimation, With<Player>>,
+    action_state: Res<ActionState>,
     level_collisions: Res<LevelCollisions>,
     time: Res<Time>,
+    #[cfg(feature = "dev_console")] dev_cheats: Res<DevCheats>,
 ) {
     let mut direction = (0.0, 0.0);
-    let mut x_correction = 0.0;
-    let mut y_correction = 0.0;
+    let mut animation_state = None;
 
-    if let Ok(mut player_transform) = player_transform.get_single_mut() {
+    if let Ok((mut player_position, hitbox, mut knockback)) = player_position.get_single_mut() {
         if let Ok(mut player_grid_pos) = player_grid_pos.get_single_mut() {
-            if input.pressed(KeyCode::KeyA) || input.pressed(KeyCode::ArrowLeft) {
+            if action_state.pressed(Action::MoveLeft) {
                 direction.0 = -1.0;
-                x_correction = -CORRECTION;
-
-                if let Ok(mut player_atlas) = player_atlas.get_single_mut() {
-                    player_atlas.index = PLAYER_ATLAS_INDEX_LEFT;
-                }
+                animation_state = Some(AnimationState::WalkLeft);
             }
 
-            if input.pressed(KeyCode::KeyD) || input.pressed(KeyCode::ArrowRight) {
+            if action_state.pressed(Action::MoveRight) {
                 direction.0 = 1.0;
-                x_correction = CORRECTION;
-
-                if let Ok(mut player_atlas) = player_atlas.get_single_mut() {
-                    player_atlas.index = PLAYER_ATLAS_INDEX_RIGHT;
-                }
+                animation_state = Some(AnimationState::WalkRight);
             }
 
-            if input.pressed(KeyCode::KeyW) || input.pressed(KeyCode::ArrowUp) {
+            if action_state.pressed(Action::MoveUp) {
                 direction.1 = 1.0;
-                y_correction = CORRECTION;
-
-                if let Ok(mut player_atlas) = player_atlas.get_single_mut() {
-                    player_atlas.index = PLAYER_ATLAS_INDEX_UP;
-                }
+                animation_state = Some(AnimationState::WalkUp);
             }
 
-            if input.pressed(KeyCode::KeyS) || input.pressed(KeyCode::ArrowDown) {
+            if action_state.pressed(Action::MoveDown) {
                 direction.1 = -1.0;
-                y_correction = -CORRECTION;
+                animation_state = Some(AnimationState::WalkDown);
+            }
 
-                if let Ok(mut player_atlas) = player_atlas.get_single_mut() {
-                    player_atlas.index = PLAYER_ATLAS_INDEX_DOWN;
-                }
+            if let Ok(mut player_animation) = player_animation.get_single_mut() {
+                player_animation.state =
+                    animation_state.unwrap_or_else(|| player_animation.state.idle());
             }
 
             if (direction.0 as i32).abs() + (direction.1 as i32).abs() > 1 {
@@ -97,24 +170,32 @@ pub(crate) fn move_player(
                 direction.1 *= 0.75;
             }
 
-            let new_player_translation_x =
-                player_transform.translation.x + direction.0 * PLAYER_SPEED * time.delta_seconds();
-            let new_player_translation_y =
-                player_transform.translation.y + direction.1 * PLAYER_SPEED * time.delta_seconds();
-            let new_player_transform =
-                Transform::from_xyz(new_player_translation_x, new_player_translation_y, 0.0);
-            let new_player_grid_pos = bevy_ecs_ldtk::utils::translation_to_grid_coords(
-                Vec2::new(
-                    new_player_transform.translation.truncate().x + x_correction,
-                    new_player_transform.translation.truncate().y + y_correction,
-                ),
-                IVec2::from((GRID_SIZE, GRID_SIZE)),
-            );
+            let velocity = (Vec2::new(direction.0, direction.1) * PLAYER_SPEED + knockback.0)
+                * time.delta_seconds();
+            knockback.0 *= (-PLAYER_KNOCKBACK_DECAY * time.delta_seconds()).exp();
+            let steps = (velocity.length() / MAX_MOVE_STEP).ceil().max(1.0);
+            let step = velocity / steps;
+
+            #[cfg(feature = "dev_console")]
+            if dev_cheats.noclip {
+                player_position.current += velocity.extend(0.0);
+                player_grid_pos.set_if_neq(bevy_ecs_ldtk::utils::translation_to_grid_coords(
+                    player_position.current.truncate(),
+                    IVec2::from((GRID_SIZE, GRID_SIZE)),
+                ));
+                return;
+            }
 
-            if !level_collisions.collision(&new_player_grid_pos) {
-                *player_grid_pos = new_player_grid_pos;
-                player_transform.translation = new_player_transform.translation;
+            for _ in 0..steps as usize {
+                player_position.current = level_collisions
+                    .move_and_slide(player_position.current.truncate(), hitbox.half_size, step)
+                    .extend(player_position.current.z);
             }
+
+            player_grid_pos.set_if_neq(bevy_ecs_ldtk::utils::translation_to_grid_coords(
+                player_position.current.truncate(),
+                IVec2::from((GRID_SIZE, GRID_SIZE)),
+            ));
         }
     }
 }
@@ -122,31 +203,16 @@ pub(crate) fn move_player(
 pub(crate) fn update_player_stats(
     mut player_stats: ResMut<PlayerStats>,
     time: Res<Time>,
-    player_grid_pos: Query<&GridCoords, (With<Player>, Changed<GridCoords>)>,
-    cows_transforms: Query<&Transform, With<Cow>>,
-    mut commands: Commands,
-    asset_server: Res<AssetServer>,
     level_selection: Res<LevelSelection>,
+    #[cfg(feature = "dev_console")] dev_cheats: Res<DevCheats>,
 ) {
     player_stats.hit_timer.tick(time.delta());
+    player_stats.shove_timer.tick(time.delta());
+    player_stats.time_played.tick(time.delta());
 
-    if let Ok(player_grid_pos) = player_grid_pos.get_single() {
-        for cow_transform in &cows_transforms {
-            let cow_grid_pos = bevy_ecs_ldtk::utils::translation_to_grid_coords(
-                cow_transform.translation.truncate(),
-                IVec2::from((GRID_SIZE, GRID_SIZE)),
-            );
-
-            if *player_grid_pos == cow_grid_pos && player_stats.hit_timer.finished() {
-                player_stats.score -= COW_SCORE_HIT;
-                player_stats.health -= COW_HEALTH_HIT;
-                commands.spawn(AudioBundle {
-                    source: asset_server.load(HIT_SOUND_PATH),
-                    ..default()
-                });
-                player_stats.hit_timer.reset();
-            }
-        }
+    #[cfg(feature = "dev_console")]
+    if dev_cheats.god {
+        return;
     }
 
     let level = match level_selection.into_inner() {
@@ -156,13 +222,172 @@ pub(crate) fn update_player_stats(
     player_stats.health -= time.delta_seconds() / (((level as f32 + 1.0) * 2.0) / 5.0);
 }
 
+pub(crate) fn check_hostile_contacts(
+    mut player_stats: ResMut<PlayerStats>,
+    player: Query<(&SimulatedPosition, &Hitbox), With<Player>>,
+    hostiles: Query<(&SimulatedPosition, &Hitbox, &Hostile), (Without<Player>, Without<Stunned>)>,
+    mut player_hits: EventWriter<PlayerHit>,
+    #[cfg(feature = "dev_console")] dev_cheats: Res<DevCheats>,
+) {
+    #[cfg(feature = "dev_console")]
+    if dev_cheats.god {
+        return;
+    }
+
+    let Ok((player_position, player_hitbox)) = player.get_single() else {
+        return;
+    };
+    let player_position = player_position.current.truncate();
+
+    for (hostile_position, hostile_hitbox, hostile) in &hostiles {
+        let hostile_position = hostile_position.current.truncate();
+
+        if player_stats.hit_timer.finished()
+            && player_hitbox.overlaps(player_position, hostile_hitbox, hostile_position)
+        {
+            player_hits.send(PlayerHit {
+                damage: hostile.damage,
+                score: hostile.score,
+                knockback: (player_position - hostile_position).normalize_or(Vec2::Y),
+            });
+            player_stats.hit_timer.reset();
+        }
+    }
+}
+
+pub(crate) fn shove(
+    action_state: Res<ActionState>,
+    mut player_stats: ResMut<PlayerStats>,
+    player: Query<&SimulatedPosition, With<Player>>,
+    mut enemies: Query<
+        (
+            Entity,
+            &mut SimulatedPosition,
+            Option<&Hitbox>,
+            &mut Sprite,
+            Has<Stunned>,
+        ),
+        (
+            Or<(With<Patrol>, With<Chicken>, With<Shooter>)>,
+            Without<Player>,
+        ),
+    >,
+    level_collisions: Res<LevelCollisions>,
+    mut commands: Commands,
+) {
+    if !action_state.just_pressed(Action::Shove) || player_stats.shove_cooldown().is_some() {
+        return;
+    }
+
+    let Ok(player_position) = player.get_single() else {
+        return;
+    };
+    let player_position = player_position.current.truncate();
+    player_stats.shove_timer.reset();
+
+    for (enemy, mut position, hitbox, mut sprite, stunned) in &mut enemies {
+        let enemy_position = position.current.truncate();
+
+        if enemy_position.distance(player_position) > SHOVE_RANGE {
+            continue;
+        }
+
+        if let Some(hitbox) = hitbox {
+            let push = (enemy_position - player_position).normalize_or(Vec2::Y) * SHOVE_DISTANCE;
+            let steps = (push.length() / MAX_MOVE_STEP).ceil().max(1.0);
+
+            for _ in 0..steps as usize {
+                position.current = level_collisions
+                    .move_and_slide(position.current.truncate(), hitbox.half_size, push / steps)
+                    .extend(position.current.z);
+            }
+        }
+
+        if !stunned {
+            player_stats.score += SHOVE_STUN_SCORE;
+        }
+
+        sprite.color = STUNNED_COLOR;
+        commands.entity(enemy).insert(Stunned::default());
+    }
+}
+
+pub(crate) fn apply_player_hits(
+    mut player_hits: EventReader<PlayerHit>,
+    mut player_stats: ResMut<PlayerStats>,
+    mut player_knockback: Query<&mut Knockback, With<Player>>,
+) {
+    for player_hit in player_hits.read() {
+        player_stats.score -= player_hit.score;
+        player_stats.health -= player_hit.damage;
+
+        if let Ok(mut knockback) = player_knockback.get_single_mut() {
+            knockback.0 = player_hit.knockback * PLAYER_KNOCKBACK_SPEED;
+        }
+    }
+}
+
+pub(crate) fn play_hit_sound(
+    mut player_hits: EventReader<PlayerHit>,
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+) {
+    for _ in player_hits.read() {
+        commands.spawn(AudioBundle {
+            source: asset_server.load(HIT_SOUND_PATH),
+            ..default()
+        });
+    }
+}
+
+pub(crate) fn flash_hurt_player(
+    player_stats: Res<PlayerStats>,
+    mut player_sprite: Query<&mut Sprite, With<Player>>,
+) {
+    if let Ok(mut player_sprite) = player_sprite.get_single_mut() {
+        player_sprite.color = match player_stats.hurt() {
+            Some(elapsed) if ((elapsed * PLAYER_HURT_FLASH_RATE) as u32).is_multiple_of(2) => {
+                HURT_COLOR
+            }
+            _ => Color::WHITE,
+        };
+    }
+}
+
+#[derive(Resource)]
+pub(crate) struct CameraShake(Timer);
+
+impl Default for CameraShake {
+    fn default() -> Self {
+        CameraShake(finished_timer(CAMERA_SHAKE_DURATION))
+    }
+}
+
+pub(crate) fn shake_camera(
+    mut player_hits: EventReader<PlayerHit>,
+    mut camera_shake: ResMut<CameraShake>,
+    time: Res<Time>,
+) {
+    if player_hits.read().count() > 0 {
+        camera_shake.0.reset();
+    }
+
+    camera_shake.0.tick(time.delta());
+}
+
 pub(crate) fn center_camera(
     player_transform: Query<&Transform, With<Player>>,
     mut camera_transform: Query<&mut Transform, (With<MainCamera>, Without<Player>)>,
+    camera_shake: Res<CameraShake>,
 ) {
     if let Ok(player_transform) = player_transform.get_single() {
         if let Ok(mut camera_transform) = camera_transform.get_single_mut() {
-            camera_transform.translation = player_transform.translation;
+            let elapsed = camera_shake.0.elapsed_secs();
+            let offset = Vec2::new((elapsed * 90.0).sin(), (elapsed * 70.0).cos())
+                * CAMERA_SHAKE_INTENSITY
+                * camera_shake.0.fraction_remaining();
+
+            camera_transform.translation = player_transform.translation + offset.extend(0.0);
         }
     }
 }
@@ -0,0 +1,127 @@
+use bevy::prelude::*;
+use bevy_ecs_ldtk::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use crate::consts::*;
+use crate::gameplay::{BackgroundMusic, GameState};
+use crate::input::{Action, ActionState};
+use crate::player::PlayerStats;
+use crate::storage;
+
+const SAVE_VERSION: u32 = 1;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(crate) struct SaveData {
+    version: u32,
+    pub(crate) level: usize,
+    pub(crate) score: f32,
+    pub(crate) health: f32,
+    pub(crate) keys_collected: usize,
+    pub(crate) time_played: f32,
+}
+
+impl SaveData {
+    pub(crate) fn new(player_stats: &PlayerStats, level: usize) -> Self {
+        SaveData {
+            version: SAVE_VERSION,
+            level,
+            score: player_stats.score,
+            health: player_stats.health,
+            keys_collected: player_stats.keys_collected,
+            time_played: player_stats.time_played.elapsed_secs(),
+        }
+    }
+
+    fn migrate(save: serde_json::Value) -> Option<Self> {
+        let version = save.get("version")?.as_u64()?;
+
+        match u32::try_from(version).ok()? {
+            SAVE_VERSION => serde_json::from_value(save).ok(),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Default, Resource)]
+pub(crate) struct SavedGame(pub(crate) Option<SaveData>);
+
+impl SavedGame {
+    pub(crate) fn load() -> Self {
+        SavedGame(
+            storage::read(SAVE_FILE_NAME)
+                .and_then(|contents| serde_json::from_str::<serde_json::Value>(&contents).ok())
+                .and_then(SaveData::migrate),
+        )
+    }
+
+    pub(crate) fn store(&mut self, save_data: SaveData) {
+        match serde_json::to_string(&save_data) {
+            Ok(contents) => storage::write(SAVE_FILE_NAME, &contents),
+            Err(err) => warn!("failed to serialize save data: {}", err),
+        }
+
+        self.0 = Some(save_data);
+    }
+}
+
+pub(crate) fn continue_game(
+    action_state: Res<ActionState>,
+    saved_game: Res<SavedGame>,
+    mut player_stats: ResMut<PlayerStats>,
+    mut level_selection: ResMut<LevelSelection>,
+    mut next_state: ResMut<NextState<GameState>>,
+    audio_sink: Query<&AudioSink, With<BackgroundMusic>>,
+) {
+    if !action_state.just_pressed(Action::Continue) {
+        return;
+    }
+
+    if let Some(save_data) = &saved_game.0 {
+        *player_stats = PlayerStats::default();
+        player_stats.score = save_data.score;
+        player_stats.health = save_data.health;
+        player_stats.keys_collected = save_data.keys_collected;
+        player_stats
+            .time_played
+            .set_elapsed(Duration::from_secs_f32(save_data.time_played));
+        player_stats.level_start_score = save_data.score;
+        *level_selection = LevelSelection::index(save_data.level);
+        next_state.set(GameState::Running);
+
+        if let Ok(audio_sink) = audio_sink.get_single() {
+            audio_sink.play();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn current_saves_round_trip() {
+        let mut player_stats = PlayerStats::default();
+        player_stats.score = 120.0;
+        player_stats.keys_collected = 4;
+        player_stats
+            .time_played
+            .set_elapsed(Duration::from_secs_f32(42.0));
+
+        let save = serde_json::to_value(SaveData::new(&player_stats, 1)).unwrap();
+        let save_data = SaveData::migrate(save).unwrap();
+        assert_eq!(save_data.level, 1);
+        assert_eq!(save_data.keys_collected, 4);
+        assert_eq!(save_data.time_played, 42.0);
+    }
+
+    #[test]
+    fn unknown_versions_are_rejected() {
+        assert!(SaveData::migrate(serde_json::json!({ "version": 99, "level": 0 })).is_none());
+        assert!(
+            SaveData::migrate(serde_json::json!({ "version": (1u64 << 32) + 1, "level": 0 }))
+                .is_none()
+        );
+        assert!(SaveData::migrate(serde_json::json!({ "level": 0 })).is_none());
+    }
+}
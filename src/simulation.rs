@@ -0,0 +1,39 @@
+use bevy::prelude::*;
+
+#[derive(Default, Component)]
+pub(crate) struct SimulatedPosition {
+    pub(crate) previous: Vec3,
+    pub(crate) current: Vec3,
+}
+
+impl SimulatedPosition {
+    pub(crate) fn teleport(&mut self, translation: Vec3) {
+        self.previous = translation;
+        self.current = translation;
+    }
+}
+
+pub(crate) fn init_simulated_positions(
+    mut query: Query<(&Transform, &mut SimulatedPosition), Added<SimulatedPosition>>,
+) {
+    for (transform, mut position) in &mut query {
+        position.teleport(transform.translation);
+    }
+}
+
+pub(crate) fn store_previous_positions(mut query: Query<&mut SimulatedPosition>) {
+    for mut position in &mut query {
+        position.previous = position.current;
+    }
+}
+
+pub(crate) fn interpolate_transforms(
+    mut query: Query<(&mut Transform, &SimulatedPosition)>,
+    fixed_time: Res<Time<Fixed>>,
+) {
+    let alpha = fixed_time.overstep_fraction();
+
+    for (mut transform, position) in &mut query {
+        transform.translation = position.previous.lerp(position.current, alpha);
+    }
+}
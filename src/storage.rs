@@ -0,0 +1,59 @@
+use bevy::prelude::*;
+
+use crate::consts::*;
+
+#[cfg(not(target_arch = "wasm32"))]
+fn path(name: &str) -> Option<std::path::PathBuf> {
+    Some(dirs::data_dir()?.join(DATA_DIR_NAME).join(name))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn read(name: &str) -> Option<String> {
+    std::fs::read_to_string(path(name)?).ok()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn write(name: &str, contents: &str) {
+    let Some(path) = path(name) else {
+        warn!("no data directory available to store {}", name);
+        return;
+    };
+
+    if let Some(parent) = path.parent() {
+        if let Err(err) = std::fs::create_dir_all(parent) {
+            warn!("failed to create {}: {}", parent.display(), err);
+            return;
+        }
+    }
+
+    if let Err(err) = std::fs::write(&path, contents) {
+        warn!("failed to write {}: {}", path.display(), err);
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn local_storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok()?
+}
+
+#[cfg(target_arch = "wasm32")]
+fn key(name: &str) -> String {
+    format!("{}/{}", DATA_DIR_NAME, name)
+}
+
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn read(name: &str) -> Option<String> {
+    local_storage()?.get_item(&key(name)).ok()?
+}
+
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn write(name: &str, contents: &str) {
+    let Some(local_storage) = local_storage() else {
+        warn!("no local storage available to store {}", name);
+        return;
+    };
+
+    if local_storage.set_item(&key(name), contents).is_err() {
+        warn!("failed to write {} to local storage", name);
+    }
+}
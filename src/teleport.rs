@@ -0,0 +1,151 @@
+use bevy::prelude::*;
+use bevy_ecs_ldtk::prelude::*;
+
+use crate::consts::*;
+use crate::gameplay::{GameState, PortalCooldown};
+use crate::player::Player;
+use crate::simulation::SimulatedPosition;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub(crate) enum TeleportPhase {
+    #[default]
+    Dissolve,
+    FadeOut,
+    FadeIn,
+    Reappear,
+}
+
+#[derive(Resource)]
+pub(crate) struct Teleport {
+    destination: Option<(Entity, GridCoords)>,
+    phase: TeleportPhase,
+    timer: Timer,
+}
+
+impl Default for Teleport {
+    fn default() -> Self {
+        Teleport {
+            destination: None,
+            phase: TeleportPhase::default(),
+            timer: Timer::from_seconds(TELEPORT_PHASE_DURATION, TimerMode::Once),
+        }
+    }
+}
+
+impl Teleport {
+    pub(crate) fn start(&mut self, destination: Entity, destination_grid_pos: GridCoords) {
+        *self = Teleport {
+            destination: Some((destination, destination_grid_pos)),
+            ..default()
+        };
+    }
+}
+
+#[derive(Component)]
+pub(crate) struct ScreenFade;
+
+pub(crate) fn setup_screen_fade(mut commands: Commands) {
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                ..default()
+            },
+            background_color: Color::NONE.into(),
+            z_index: ZIndex::Global(5),
+            ..default()
+        })
+        .insert(ScreenFade);
+}
+
+pub(crate) fn run_teleport(
+    mut teleport: ResMut<Teleport>,
+    mut player: Query<
+        (
+            &mut SimulatedPosition,
+            &mut GridCoords,
+            &mut Sprite,
+            &mut PortalCooldown,
+        ),
+        With<Player>,
+    >,
+    mut screen_fade: Query<&mut BackgroundColor, With<ScreenFade>>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    time: Res<Time>,
+) {
+    let Some((destination, destination_grid_pos)) = teleport.destination else {
+        next_state.set(GameState::Running);
+        return;
+    };
+    let Ok((mut player_position, mut player_grid_pos, mut player_sprite, mut portal_cooldown)) =
+        player.get_single_mut()
+    else {
+        return;
+    };
+
+    teleport.timer.tick(time.delta());
+    let progress = teleport.timer.fraction();
+
+    let (player_alpha, fade_alpha) = match teleport.phase {
+        TeleportPhase::Dissolve => (1.0 - progress, 0.0),
+        TeleportPhase::FadeOut => (0.0, progress),
+        TeleportPhase::FadeIn => (0.0, 1.0 - progress),
+        TeleportPhase::Reappear => (progress, 0.0),
+    };
+    player_sprite.color = Color::WHITE.with_alpha(player_alpha);
+
+    if let Ok(mut background_color) = screen_fade.get_single_mut() {
+        background_color.0 = Color::BLACK.with_alpha(fade_alpha);
+    }
+
+    if !teleport.timer.finished() {
+        return;
+    }
+
+    teleport.timer.reset();
+    teleport.phase = match teleport.phase {
+        TeleportPhase::Dissolve => TeleportPhase::FadeOut,
+        TeleportPhase::FadeOut => {
+            let translation = bevy_ecs_ldtk::utils::grid_coords_to_translation(
+                destination_grid_pos,
+                IVec2::new(GRID_SIZE, GRID_SIZE),
+            )
+            .extend(player_position.current.z);
+            player_position.teleport(translation);
+            *player_grid_pos = destination_grid_pos;
+            portal_cooldown.arrive(destination);
+
+            commands.spawn(AudioBundle {
+                source: asset_server.load(TELEPORT_SOUND_PATH),
+                ..default()
+            });
+
+            TeleportPhase::FadeIn
+        }
+        TeleportPhase::FadeIn => TeleportPhase::Reappear,
+        TeleportPhase::Reappear => {
+            next_state.set(GameState::Running);
+            TeleportPhase::Reappear
+        }
+    };
+}
+
+pub(crate) fn clear_teleport(
+    mut teleport: ResMut<Teleport>,
+    mut player_sprite: Query<&mut Sprite, With<Player>>,
+    mut screen_fade: Query<&mut BackgroundColor, With<ScreenFade>>,
+) {
+    *teleport = Teleport::default();
+
+    if let Ok(mut player_sprite) = player_sprite.get_single_mut() {
+        player_sprite.color = Color::WHITE;
+    }
+
+    if let Ok(mut background_color) = screen_fade.get_single_mut() {
+        background_color.0 = Color::NONE;
+    }
+}
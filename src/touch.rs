@@ -0,0 +1,152 @@
+use bevy::{prelude::*, utils::HashSet};
+
+use crate::consts::*;
+use crate::input::Action;
+
+#[derive(Component)]
+pub(crate) struct TouchControls;
+
+#[derive(Component)]
+pub(crate) struct TouchButton(Action);
+
+#[derive(Default, Resource)]
+pub(crate) struct TouchActions(pub(crate) HashSet<Action>);
+
+fn spawn_touch_button(
+    parent: &mut ChildBuilder,
+    asset_server: &AssetServer,
+    action: Action,
+    label: &str,
+    style: Style,
+) {
+    parent
+        .spawn(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                width: Val::Vh(TOUCH_BUTTON_SIZE),
+                height: Val::Vh(TOUCH_BUTTON_SIZE),
+                ..style
+            },
+            background_color: TOUCH_BUTTON_COLOR.into(),
+            border_radius: BorderRadius::all(Val::Percent(25.0)),
+            ..default()
+        })
+        .insert(TouchButton(action))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                label,
+                TextStyle {
+                    font: asset_server.load("fonts/FiraCodeNerdFont-Regular.ttf"),
+                    font_size: 60.0,
+                    color: TEXT_COLOR,
+                },
+            ));
+        });
+}
+
+pub(crate) fn setup_touch_controls(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                ..default()
+            },
+            visibility: Visibility::Hidden,
+            z_index: ZIndex::Global(10),
+            ..default()
+        })
+        .insert(TouchControls)
+        .with_children(|parent| {
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        left: Val::Vh(TOUCH_BUTTON_SIZE / 3.0),
+                        bottom: Val::Vh(TOUCH_BUTTON_SIZE / 3.0),
+                        width: Val::Vh(TOUCH_BUTTON_SIZE * 3.0),
+                        height: Val::Vh(TOUCH_BUTTON_SIZE * 3.0),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    for (action, label, left, top) in [
+                        (Action::MoveUp, "", 1.0, 0.0),
+                        (Action::MoveLeft, "", 0.0, 1.0),
+                        (Action::MoveRight, "", 2.0, 1.0),
+                        (Action::MoveDown, "", 1.0, 2.0),
+                    ] {
+                        spawn_touch_button(
+                            parent,
+                            &asset_server,
+                            action,
+                            label,
+                            Style {
+                                left: Val::Vh(TOUCH_BUTTON_SIZE * left),
+                                top: Val::Vh(TOUCH_BUTTON_SIZE * top),
+                                ..default()
+                            },
+                        );
+                    }
+                });
+
+            spawn_touch_button(
+                parent,
+                &asset_server,
+                Action::Pause,
+                "",
+                Style {
+                    right: Val::Vh(TOUCH_BUTTON_SIZE / 3.0),
+                    top: Val::Vh(TOUCH_BUTTON_SIZE),
+                    ..default()
+                },
+            );
+
+            spawn_touch_button(
+                parent,
+                &asset_server,
+                Action::Shove,
+                "",
+                Style {
+                    right: Val::Vh(TOUCH_BUTTON_SIZE),
+                    bottom: Val::Vh(TOUCH_BUTTON_SIZE),
+                    ..default()
+                },
+            );
+        });
+}
+
+pub(crate) fn show_touch_controls(
+    touches: Res<Touches>,
+    mut touch_controls: Query<&mut Visibility, With<TouchControls>>,
+) {
+    if touches.any_just_pressed() {
+        if let Ok(mut visibility) = touch_controls.get_single_mut() {
+            *visibility = Visibility::Visible;
+        }
+    }
+}
+
+pub(crate) fn read_touch_buttons(
+    touches: Res<Touches>,
+    touch_buttons: Query<(&TouchButton, &Node, &GlobalTransform, &ViewVisibility)>,
+    mut touch_actions: ResMut<TouchActions>,
+) {
+    touch_actions.0.clear();
+
+    for (touch_button, node, transform, view_visibility) in &touch_buttons {
+        if !view_visibility.get() {
+            continue;
+        }
+
+        let rect = node.logical_rect(transform);
+
+        if touches.iter().any(|touch| rect.contains(touch.position())) {
+            touch_actions.0.insert(touch_button.0);
+        }
+    }
+}
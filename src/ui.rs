@@ -2,11 +2,21 @@ use bevy::prelude::*;
 use bevy_ecs_ldtk::prelude::*;
 
 use crate::consts::*;
+use crate::gameplay::{GameOverStats, GameState};
+use crate::highscores::{format_date, HighScores, PlayerName};
+use crate::input::{Action, InputBindings, Rebinding};
+use crate::levels::{
+    level_name, LdtkProjectHandle, LevelProgress, LevelSelectCursor, LevelSummary,
+};
 use crate::player::PlayerStats;
+use crate::save::SavedGame;
 
 #[derive(Component)]
 pub(crate) struct Menu;
 
+#[derive(Component)]
+pub(crate) struct StatusBar;
+
 #[derive(Component)]
 pub(crate) struct ScoreText;
 
@@ -19,6 +29,18 @@ pub(crate) struct KeysText;
 #[derive(Component)]
 pub(crate) struct LevelText;
 
+#[derive(Component)]
+pub(crate) struct ShoveText;
+
+#[derive(Component)]
+pub(crate) struct NameText;
+
+#[derive(Component)]
+pub(crate) struct ControlsText(usize);
+
+#[derive(Component)]
+pub(crate) struct LevelSelectText(usize);
+
 pub(crate) fn setup_status_bar(mut commands: Commands, asset_server: Res<AssetServer>) {
     commands
         .spawn(NodeBundle {
@@ -33,6 +55,7 @@ pub(crate) fn setup_status_bar(mut commands: Commands, asset_server: Res<AssetSe
             background_color: BAR_COLOR.into(),
             ..default()
         })
+        .insert(StatusBar)
         .with_children(|parent| {
             parent
                 .spawn(TextBundle::from_section(
@@ -74,9 +97,25 @@ pub(crate) fn setup_status_bar(mut commands: Commands, asset_server: Res<AssetSe
                     },
                 ))
                 .insert(LevelText);
+            parent
+                .spawn(TextBundle::from_section(
+                    " READY",
+                    TextStyle {
+                        font: asset_server.load("fonts/FiraCodeNerdFont-Regular.ttf"),
+                        font_size: 40.0,
+                        color: TEXT_COLOR,
+                    },
+                ))
+                .insert(ShoveText);
         });
 }
 
+pub(crate) fn despawn_status_bar(mut commands: Commands, query: Query<Entity, With<StatusBar>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
 pub(crate) fn update_status_bar(
     player_stats: Res<PlayerStats>,
     level_selection: ResMut<LevelSelection>,
@@ -92,12 +131,26 @@ pub(crate) fn update_status_bar(
             Without<KeysText>,
         ),
     >,
+    mut shove_query: Query<
+        &mut Text,
+        (
+            With<ShoveText>,
+            Without<ScoreText>,
+            Without<HealthText>,
+            Without<KeysText>,
+            Without<LevelText>,
+        ),
+    >,
 ) {
     if let Ok(mut text) = score_query.get_single_mut() {
         text.sections[0].value = format!("󱉾 {:05.0}", player_stats.score);
     }
 
     if let Ok(mut text) = health_query.get_single_mut() {
+        text.sections[0].style.color = match player_stats.hurt() {
+            Some(_) => HURT_COLOR,
+            None => TEXT_COLOR,
+        };
         text.sections[0].value = format!(
             " {:03.0}",
             if player_stats.health > 0.0 {
@@ -120,9 +173,22 @@ pub(crate) fn update_status_bar(
     if let Ok(mut text) = level_query.get_single_mut() {
         text.sections[0].value = format!("󰬓 {:02}", level);
     }
+
+    if let Ok(mut text) = shove_query.get_single_mut() {
+        text.sections[0].value = match player_stats.shove_cooldown() {
+            Some(remaining) => format!(" {:.1}", remaining),
+            None => " READY".to_string(),
+        };
+    }
 }
 
-pub(crate) fn setup_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
+pub(crate) fn setup_menu(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    game_state: Res<State<GameState>>,
+    saved_game: Res<SavedGame>,
+    input_bindings: Res<InputBindings>,
+) {
     commands
         .spawn(NodeBundle {
             style: Style {
@@ -150,13 +216,135 @@ pub(crate) fn setup_menu(mut commands: Commands, asset_server: Res<AssetServer>)
                 ))
                 .insert(Menu);
         });
+
+    let mut items = Vec::new();
+
+    if *game_state.get() == GameState::Menu {
+        if let Some(save_data) = &saved_game.0 {
+            items.push(format!(
+                "{} - CONTINUE FROM LEVEL {}",
+                input_bindings.describe(Action::Continue),
+                save_data.level + 1
+            ));
+        }
+
+        items.push(format!(
+            "{} - HIGH SCORES",
+            input_bindings.describe(Action::HighScores)
+        ));
+        items.push(format!(
+            "{} - CONTROLS",
+            input_bindings.describe(Action::Controls)
+        ));
+        items.push(format!(
+            "{} - LEVEL SELECT",
+            input_bindings.describe(Action::LevelSelect)
+        ));
+    }
+
+    items.extend([
+        format!(
+            "{} - START/PAUSE GAME",
+            input_bindings.describe(Action::Pause)
+        ),
+        format!(
+            "{}{}{}{} - MOVE PLAYER AROUND",
+            input_bindings.describe(Action::MoveLeft),
+            input_bindings.describe(Action::MoveUp),
+            input_bindings.describe(Action::MoveDown),
+            input_bindings.describe(Action::MoveRight)
+        ),
+        format!(
+            "{} - TOGGLE MUSIC ON/OFF",
+            input_bindings.describe(Action::ToggleMusic)
+        ),
+        format!(
+            "{} - RESTART GAME",
+            input_bindings.describe(Action::Restart)
+        ),
+        format!("{} - QUIT GAME", input_bindings.describe(Action::Quit)),
+    ]);
+
     commands
         .spawn(NodeBundle {
             style: Style {
+                position_type: PositionType::Absolute,
                 left: Val::Percent(25.0),
                 top: Val::Percent(30.0),
-                justify_content: JustifyContent::Start,
-                align_items: AlignItems::Start,
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Percent(1.5),
+                width: Val::Percent(50.0),
+                ..default()
+            },
+            ..default()
+        })
+        .insert(Menu)
+        .with_children(|parent| {
+            for item in items {
+                parent
+                    .spawn(NodeBundle {
+                        style: Style {
+                            justify_content: JustifyContent::Start,
+                            align_items: AlignItems::Start,
+                            padding: UiRect::all(Val::Percent(1.0)),
+                            ..default()
+                        },
+                        background_color: BAR_COLOR.into(),
+                        border_radius: BorderRadius::all(Val::Percent(12.5)),
+                        ..default()
+                    })
+                    .insert(Menu)
+                    .with_children(|parent| {
+                        parent
+                            .spawn(TextBundle::from_section(
+                                item,
+                                TextStyle {
+                                    font: asset_server.load("fonts/FiraCodeNerdFont-Regular.ttf"),
+                                    font_size: 50.0,
+                                    color: TEXT_COLOR,
+                                },
+                            ))
+                            .insert(Menu);
+                    });
+            }
+        });
+}
+
+pub(crate) fn despawn_menu(mut commands: Commands, query: Query<Entity, With<Menu>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+pub(crate) fn setup_game_over(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    game_over_stats: Res<GameOverStats>,
+    input_bindings: Res<InputBindings>,
+) {
+    let time_played = game_over_stats.time_played as u32;
+    let lines = [
+        format!("󱉾 SCORE - {:05.0}", game_over_stats.score),
+        format!("󰬓 LEVEL - {:02}", game_over_stats.level),
+        format!("󱕴 KEYS - {:02}", game_over_stats.keys_collected),
+        format!("󰅐 TIME - {:02}:{:02}", time_played / 60, time_played % 60),
+        String::new(),
+        format!("{} - RETRY LEVEL", input_bindings.describe(Action::Retry)),
+        format!(
+            "{} - RESTART FROM LEVEL 1",
+            input_bindings.describe(Action::Restart)
+        ),
+        format!("{} - MAIN MENU", input_bindings.describe(Action::Back)),
+    ];
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                left: Val::Percent(25.0),
+                top: Val::Percent(15.0),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
                 width: Val::Percent(50.0),
                 padding: UiRect::all(Val::Percent(1.0)),
                 ..default()
@@ -169,22 +357,62 @@ pub(crate) fn setup_menu(mut commands: Commands, asset_server: Res<AssetServer>)
         .with_children(|parent| {
             parent
                 .spawn(TextBundle::from_section(
-                    "SPACE - START/PAUSE GAME",
+                    "GAME OVER",
                     TextStyle {
-                        font: asset_server.load("fonts/FiraCodeNerdFont-Regular.ttf"),
-                        font_size: 50.0,
-                        color: TEXT_COLOR,
+                        font_size: 150.0,
+                        color: GAME_OVER_COLOR,
+                        ..default()
                     },
                 ))
                 .insert(Menu);
+
+            for line in lines {
+                parent
+                    .spawn(TextBundle::from_section(
+                        line,
+                        TextStyle {
+                            font: asset_server.load("fonts/FiraCodeNerdFont-Regular.ttf"),
+                            font_size: 50.0,
+                            color: TEXT_COLOR,
+                        },
+                    ))
+                    .insert(Menu);
+            }
         });
+}
+
+pub(crate) fn setup_victory(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    game_over_stats: Res<GameOverStats>,
+    input_bindings: Res<InputBindings>,
+) {
+    let time_played = game_over_stats.time_played as u32;
+    let lines = [
+        format!("ALL {} LEVELS CLEARED", game_over_stats.level),
+        format!("󱉾 SCORE - {:05.0}", game_over_stats.score),
+        format!("󱕴 KEYS - {:02}", game_over_stats.keys_collected),
+        format!("󰅐 TIME - {:02}:{:02}", time_played / 60, time_played % 60),
+        String::new(),
+        format!("{} {}", APP_NAME, VERSION),
+        "MADE WITH BEVY AND LDTK".to_string(),
+        "THANKS FOR PLAYING!".to_string(),
+        String::new(),
+        format!(
+            "{} - PLAY AGAIN FROM LEVEL 1",
+            input_bindings.describe(Action::Restart)
+        ),
+        format!("{} - MAIN MENU", input_bindings.describe(Action::Back)),
+    ];
+
     commands
         .spawn(NodeBundle {
             style: Style {
                 left: Val::Percent(25.0),
-                top: Val::Percent(40.0),
-                justify_content: JustifyContent::Start,
-                align_items: AlignItems::Start,
+                top: Val::Percent(10.0),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
                 width: Val::Percent(50.0),
                 padding: UiRect::all(Val::Percent(1.0)),
                 ..default()
@@ -197,22 +425,57 @@ pub(crate) fn setup_menu(mut commands: Commands, asset_server: Res<AssetServer>)
         .with_children(|parent| {
             parent
                 .spawn(TextBundle::from_section(
-                    "AWSD - MOVE PLAYER AROUND",
+                    "VICTORY",
                     TextStyle {
-                        font: asset_server.load("fonts/FiraCodeNerdFont-Regular.ttf"),
-                        font_size: 50.0,
+                        font_size: 150.0,
                         color: TEXT_COLOR,
+                        ..default()
                     },
                 ))
                 .insert(Menu);
+
+            for line in lines {
+                parent
+                    .spawn(TextBundle::from_section(
+                        line,
+                        TextStyle {
+                            font: asset_server.load("fonts/FiraCodeNerdFont-Regular.ttf"),
+                            font_size: 50.0,
+                            color: TEXT_COLOR,
+                        },
+                    ))
+                    .insert(Menu);
+            }
         });
+}
+
+pub(crate) fn setup_level_complete(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    level_summary: Res<LevelSummary>,
+    input_bindings: Res<InputBindings>,
+) {
+    let time = level_summary.time as u32;
+    let lines = [
+        format!("󰅐 TIME - {:02}:{:02}", time / 60, time % 60),
+        format!(
+            "󰆼 BRONZE - {:02}/{:02}",
+            level_summary.bronze_collected, level_summary.bronze_total
+        ),
+        format!("󰋒 DAMAGE - {:03.0}", level_summary.damage_taken),
+        format!("󱉾 SCORE - {:+06.0}", level_summary.score_delta),
+        String::new(),
+        format!("{} - NEXT LEVEL", input_bindings.describe(Action::Confirm)),
+    ];
+
     commands
         .spawn(NodeBundle {
             style: Style {
                 left: Val::Percent(25.0),
-                top: Val::Percent(50.0),
-                justify_content: JustifyContent::Start,
-                align_items: AlignItems::Start,
+                top: Val::Percent(15.0),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
                 width: Val::Percent(50.0),
                 padding: UiRect::all(Val::Percent(1.0)),
                 ..default()
@@ -225,22 +488,44 @@ pub(crate) fn setup_menu(mut commands: Commands, asset_server: Res<AssetServer>)
         .with_children(|parent| {
             parent
                 .spawn(TextBundle::from_section(
-                    "M - TOGGLE MUSIC ON/OFF",
+                    format!("LEVEL {} COMPLETE", level_summary.level),
                     TextStyle {
-                        font: asset_server.load("fonts/FiraCodeNerdFont-Regular.ttf"),
-                        font_size: 50.0,
+                        font_size: 100.0,
                         color: TEXT_COLOR,
+                        ..default()
                     },
                 ))
                 .insert(Menu);
+
+            for line in lines {
+                parent
+                    .spawn(TextBundle::from_section(
+                        line,
+                        TextStyle {
+                            font: asset_server.load("fonts/FiraCodeNerdFont-Regular.ttf"),
+                            font_size: 50.0,
+                            color: TEXT_COLOR,
+                        },
+                    ))
+                    .insert(Menu);
+            }
         });
+}
+
+pub(crate) fn setup_name_entry(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    game_over_stats: Res<GameOverStats>,
+    input_bindings: Res<InputBindings>,
+) {
     commands
         .spawn(NodeBundle {
             style: Style {
                 left: Val::Percent(25.0),
-                top: Val::Percent(60.0),
-                justify_content: JustifyContent::Start,
-                align_items: AlignItems::Start,
+                top: Val::Percent(25.0),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
                 width: Val::Percent(50.0),
                 padding: UiRect::all(Val::Percent(1.0)),
                 ..default()
@@ -251,9 +536,142 @@ pub(crate) fn setup_menu(mut commands: Commands, asset_server: Res<AssetServer>)
         })
         .insert(Menu)
         .with_children(|parent| {
+            for (line, font_size) in [
+                ("NEW HIGH SCORE!".to_string(), 80.0),
+                (format!("󱉾 {:05.0}", game_over_stats.score), 50.0),
+                ("ENTER YOUR NAME".to_string(), 50.0),
+            ] {
+                parent
+                    .spawn(TextBundle::from_section(
+                        line,
+                        TextStyle {
+                            font: asset_server.load("fonts/FiraCodeNerdFont-Regular.ttf"),
+                            font_size,
+                            color: TEXT_COLOR,
+                        },
+                    ))
+                    .insert(Menu);
+            }
+
+            parent
+                .spawn(TextBundle::from_section(
+                    "_",
+                    TextStyle {
+                        font: asset_server.load("fonts/FiraCodeNerdFont-Regular.ttf"),
+                        font_size: 80.0,
+                        color: GAME_OVER_COLOR,
+                    },
+                ))
+                .insert((Menu, NameText));
+
+            for (line, font_size) in [
+                (
+                    format!(
+                        "{} - CONFIRM   {} - SKIP",
+                        input_bindings.describe(Action::Confirm),
+                        input_bindings.describe(Action::Back)
+                    ),
+                    50.0,
+                ),
+                ("PAD UP/DOWN - LETTER   LEFT/RIGHT - MOVE".to_string(), 30.0),
+            ] {
+                parent
+                    .spawn(TextBundle::from_section(
+                        line,
+                        TextStyle {
+                            font: asset_server.load("fonts/FiraCodeNerdFont-Regular.ttf"),
+                            font_size,
+                            color: TEXT_COLOR,
+                        },
+                    ))
+                    .insert(Menu);
+            }
+        });
+}
+
+pub(crate) fn update_name_entry(
+    player_name: Res<PlayerName>,
+    mut name_query: Query<&mut Text, With<NameText>>,
+) {
+    if let Ok(mut text) = name_query.get_single_mut() {
+        text.sections[0].value = format!("{}_", player_name.0);
+    }
+}
+
+pub(crate) fn setup_high_scores(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    high_scores: Res<HighScores>,
+    input_bindings: Res<InputBindings>,
+) {
+    let mut lines: Vec<String> = high_scores
+        .entries
+        .iter()
+        .enumerate()
+        .map(|(rank, entry)| {
+            let time_played = entry.time_played as u32;
+            format!(
+                "{:02} {:<12} 󱉾 {:05.0} 󰬓 {:02} 󰅐 {:02}:{:02} {}",
+                rank + 1,
+                entry.name,
+                entry.score,
+                entry.level,
+                time_played / 60,
+                time_played % 60,
+                format_date(entry.date)
+            )
+        })
+        .collect();
+
+    if lines.is_empty() {
+        lines.push("NO HIGH SCORES YET".to_string());
+    }
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                left: Val::Percent(15.0),
+                top: Val::Percent(8.0),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                width: Val::Percent(70.0),
+                padding: UiRect::all(Val::Percent(1.0)),
+                ..default()
+            },
+            background_color: BAR_COLOR.into(),
+            border_radius: BorderRadius::all(Val::Percent(6.0)),
+            ..default()
+        })
+        .insert(Menu)
+        .with_children(|parent| {
+            parent
+                .spawn(TextBundle::from_section(
+                    "HIGH SCORES",
+                    TextStyle {
+                        font: asset_server.load("fonts/FiraCodeNerdFont-Regular.ttf"),
+                        font_size: 80.0,
+                        color: TEXT_COLOR,
+                    },
+                ))
+                .insert(Menu);
+
+            for line in lines {
+                parent
+                    .spawn(TextBundle::from_section(
+                        line,
+                        TextStyle {
+                            font: asset_server.load("fonts/FiraCodeNerdFont-Regular.ttf"),
+                            font_size: 40.0,
+                            color: TEXT_COLOR,
+                        },
+                    ))
+                    .insert(Menu);
+            }
+
             parent
                 .spawn(TextBundle::from_section(
-                    "F5 - RESTART GAME",
+                    format!("{} - BACK", input_bindings.describe(Action::Back)),
                     TextStyle {
                         font: asset_server.load("fonts/FiraCodeNerdFont-Regular.ttf"),
                         font_size: 50.0,
@@ -262,26 +680,62 @@ pub(crate) fn setup_menu(mut commands: Commands, asset_server: Res<AssetServer>)
                 ))
                 .insert(Menu);
         });
+}
+
+pub(crate) fn setup_controls(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    input_bindings: Res<InputBindings>,
+) {
     commands
         .spawn(NodeBundle {
             style: Style {
-                left: Val::Percent(25.0),
-                top: Val::Percent(70.0),
-                justify_content: JustifyContent::Start,
+                left: Val::Percent(20.0),
+                top: Val::Percent(5.0),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
                 align_items: AlignItems::Start,
-                width: Val::Percent(50.0),
+                width: Val::Percent(60.0),
                 padding: UiRect::all(Val::Percent(1.0)),
                 ..default()
             },
             background_color: BAR_COLOR.into(),
-            border_radius: BorderRadius::all(Val::Percent(12.5)),
+            border_radius: BorderRadius::all(Val::Percent(6.0)),
             ..default()
         })
         .insert(Menu)
         .with_children(|parent| {
             parent
                 .spawn(TextBundle::from_section(
-                    "Q - QUIT GAME",
+                    "CONTROLS",
+                    TextStyle {
+                        font: asset_server.load("fonts/FiraCodeNerdFont-Regular.ttf"),
+                        font_size: 80.0,
+                        color: TEXT_COLOR,
+                    },
+                ))
+                .insert(Menu);
+
+            for index in 0..Action::ALL.len() {
+                parent
+                    .spawn(TextBundle::from_section(
+                        "",
+                        TextStyle {
+                            font: asset_server.load("fonts/FiraCodeNerdFont-Regular.ttf"),
+                            font_size: 35.0,
+                            color: TEXT_COLOR,
+                        },
+                    ))
+                    .insert((Menu, ControlsText(index)));
+            }
+
+            parent
+                .spawn(TextBundle::from_section(
+                    format!(
+                        "{} - REBIND  {} - BACK",
+                        input_bindings.describe(Action::Confirm),
+                        input_bindings.describe(Action::Back)
+                    ),
                     TextStyle {
                         font: asset_server.load("fonts/FiraCodeNerdFont-Regular.ttf"),
                         font_size: 50.0,
@@ -292,36 +746,145 @@ pub(crate) fn setup_menu(mut commands: Commands, asset_server: Res<AssetServer>)
         });
 }
 
-pub(crate) fn despawn_menu(mut commands: Commands, query: Query<Entity, With<Menu>>) {
-    for entity in query.iter() {
-        commands.entity(entity).despawn();
+pub(crate) fn update_controls(
+    rebinding: Res<Rebinding>,
+    input_bindings: Res<InputBindings>,
+    mut controls_query: Query<(&mut Text, &ControlsText)>,
+) {
+    for (mut text, controls_text) in &mut controls_query {
+        let action = Action::ALL[controls_text.0];
+        let selected = controls_text.0 == rebinding.selected;
+        let bindings = if selected && rebinding.waiting {
+            "PRESS A KEY OR BUTTON...".to_string()
+        } else {
+            input_bindings
+                .get(action)
+                .iter()
+                .map(|binding| binding.label())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+
+        text.sections[0].value = format!(
+            "{} {:<12} {}",
+            if selected { ">" } else { " " },
+            action.name(),
+            bindings
+        );
+        text.sections[0].style.color = if selected {
+            GAME_OVER_COLOR
+        } else {
+            TEXT_COLOR
+        };
     }
 }
 
-pub(crate) fn setup_game_over(mut commands: Commands) {
+pub(crate) fn setup_level_select(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    input_bindings: Res<InputBindings>,
+    ldtk_project_handle: Res<LdtkProjectHandle>,
+    ldtk_project_assets: Res<Assets<LdtkProject>>,
+) {
+    let level_count = ldtk_project_handle
+        .levels(&ldtk_project_assets)
+        .map_or(0, <[_]>::len);
+
     commands
         .spawn(NodeBundle {
             style: Style {
-                left: Val::Percent(25.0),
-                top: Val::Percent(50.0),
+                left: Val::Percent(20.0),
+                top: Val::Percent(10.0),
+                flex_direction: FlexDirection::Column,
                 justify_content: JustifyContent::Center,
-                align_items: AlignItems::Center,
-                width: Val::Percent(50.0),
+                align_items: AlignItems::Start,
+                width: Val::Percent(60.0),
+                padding: UiRect::all(Val::Percent(1.0)),
                 ..default()
             },
+            background_color: BAR_COLOR.into(),
+            border_radius: BorderRadius::all(Val::Percent(6.0)),
             ..default()
         })
         .insert(Menu)
         .with_children(|parent| {
             parent
                 .spawn(TextBundle::from_section(
-                    "GAME OVER",
+                    "LEVEL SELECT",
                     TextStyle {
-                        font_size: 150.0,
-                        color: GAME_OVER_COLOR,
-                        ..default()
+                        font: asset_server.load("fonts/FiraCodeNerdFont-Regular.ttf"),
+                        font_size: 80.0,
+                        color: TEXT_COLOR,
+                    },
+                ))
+                .insert(Menu);
+
+            for index in 0..level_count {
+                parent
+                    .spawn(TextBundle::from_section(
+                        "",
+                        TextStyle {
+                            font: asset_server.load("fonts/FiraCodeNerdFont-Regular.ttf"),
+                            font_size: 50.0,
+                            color: TEXT_COLOR,
+                        },
+                    ))
+                    .insert((Menu, LevelSelectText(index)));
+            }
+
+            parent
+                .spawn(TextBundle::from_section(
+                    format!(
+                        "{} - PLAY  {} - BACK",
+                        input_bindings.describe(Action::Confirm),
+                        input_bindings.describe(Action::Back)
+                    ),
+                    TextStyle {
+                        font: asset_server.load("fonts/FiraCodeNerdFont-Regular.ttf"),
+                        font_size: 50.0,
+                        color: TEXT_COLOR,
                     },
                 ))
                 .insert(Menu);
         });
 }
+
+pub(crate) fn update_level_select(
+    level_select_cursor: Res<LevelSelectCursor>,
+    level_progress: Res<LevelProgress>,
+    saved_game: Res<SavedGame>,
+    ldtk_project_handle: Res<LdtkProjectHandle>,
+    ldtk_project_assets: Res<Assets<LdtkProject>>,
+    mut level_select_query: Query<(&mut Text, &LevelSelectText)>,
+) {
+    let Some(levels) = ldtk_project_handle.levels(&ldtk_project_assets) else {
+        return;
+    };
+
+    for (mut text, level_select_text) in &mut level_select_query {
+        let Some(level) = levels.get(level_select_text.0) else {
+            continue;
+        };
+        let selected = level_select_text.0 == level_select_cursor.0;
+        let status = if !level_progress.unlocked(level_select_text.0, &saved_game) {
+            "LOCKED".to_string()
+        } else if let Some(best_time) = level_progress.best_time(level) {
+            let best_time = best_time as u32;
+            format!("󰅐 {:02}:{:02}", best_time / 60, best_time % 60)
+        } else {
+            "󰅐 --:--".to_string()
+        };
+
+        text.sections[0].value = format!(
+            "{} {:<16} {}",
+            if selected { ">" } else { " " },
+            level_name(level),
+            status
+        );
+        text.sections[0].style.color = if selected {
+            GAME_OVER_COLOR
+        } else {
+            TEXT_COLOR
+        };
+    }
+}